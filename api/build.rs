@@ -1,41 +1,81 @@
 
 #![allow(clippy::expect_used, missing_docs, reason = "build script.")]
 
-use std::{path::PathBuf, };
-use core::str::FromStr as _;
+///
+/// Generates `src/cbindings.rs` from the C headers. The generated file is checked in,
+/// so that the crate builds without libclang. Enable the `bindgen` feature after changing a header.
+///
+#[cfg(feature = "bindgen")]
+mod bindings {
+    use std::path::PathBuf;
+    use core::str::FromStr as _;
 
-use bindgen_helpers::{Renamer,
-    callbacks::{ItemKind, ParseCallbacks, DeriveInfo},
-    rename_enum,
-};
+    use bindgen_helpers::{Renamer,
+        callbacks::{ItemKind, ParseCallbacks, DeriveInfo},
+        rename_enum,
+    };
 
-#[derive(Debug)]
-struct CPrefix;
+    #[derive(Debug)]
+    struct CPrefix;
 
-impl ParseCallbacks for CPrefix {
-    fn item_name(&self, item_info: bindgen_helpers::callbacks::ItemInfo) -> Option<String> {
-        if matches!(item_info.kind, ItemKind::Type) {
-            return Some(format!("C{}", item_info.name));
+    impl ParseCallbacks for CPrefix {
+        fn item_name(&self, item_info: bindgen_helpers::callbacks::ItemInfo) -> Option<String> {
+            if matches!(item_info.kind, ItemKind::Type) {
+                return Some(format!("C{}", item_info.name));
+            }
+            None
         }
-        None
     }
-}
 
-#[derive(Debug)]
-struct CloneDerive;
+    #[derive(Debug)]
+    struct CloneDerive;
 
-impl ParseCallbacks for CloneDerive {
-    fn add_derives(&self, info: &DeriveInfo<'_>) -> Vec<String> {
-        if info.name == "CApiVersion" {
-            return vec!["Clone".to_owned()];
+    impl ParseCallbacks for CloneDerive {
+        fn add_derives(&self, info: &DeriveInfo<'_>) -> Vec<String> {
+            if info.name == "CApiVersion" {
+                return vec!["Clone".to_owned()];
+            }
+            vec![]
         }
-        vec![]
+    }
+
+    pub fn generate() {
+        println!("cargo::rerun-if-changed=src/capi/header");
+
+        let mut renamer = Renamer::new(false);
+
+        rename_enum!(
+            renamer,
+            "ServiceError" => "ServiceError",
+            remove: "^SERVICE_"
+        );
+        rename_enum!(
+            renamer,
+            "EventDispatch" => "EventDispatch",
+            remove: "^DISPATCH_"
+        );
+
+        let bindings = bindgen_helpers::Builder::default()
+            .use_core()
+            .header("./src/capi/header/ft_api.h")
+            .parse_callbacks(Box::new(bindgen_helpers::CargoCallbacks::new()))
+            .derive_copy(false)
+            .default_enum_style(bindgen_helpers::EnumVariation::Rust {
+                non_exhaustive: false,
+            })
+            .parse_callbacks(Box::new(CloneDerive))
+            .parse_callbacks(Box::new(renamer))
+            .parse_callbacks(Box::new(CPrefix))
+            .generate()
+            .expect("Unable to generate c -> rust bindings!");
+        let out_path = PathBuf::from_str("./src/cbindings.rs").expect("Project structure incorrect");
+        bindings
+            .write_to_file(out_path)
+            .expect("Couldn't write bindings!");
     }
 }
 
 fn main() {
-    println!("cargo::rerun-if-changed=src/capi/header/ft_api.h");
-
     // let crate_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
 
     // cbindgen::Builder::new()
@@ -46,31 +86,8 @@ fn main() {
     //     .expect("Unable to generate rust -> c bindings!")
     //     .write_to_file("src/capi/header/ft_rustbindings.h");
 
-    let mut renamer = Renamer::new(false);
-
-    rename_enum!(
-        renamer,
-        "ServiceError" => "ServiceError",
-        remove: "^SERVICE_"
-    );
-
-    let bindings = bindgen_helpers::Builder::default()
-        .use_core()
-        .header("./src/capi/header/ft_api.h")
-        .parse_callbacks(Box::new(bindgen_helpers::CargoCallbacks::new()))
-        .derive_copy(false)
-        .default_enum_style(bindgen_helpers::EnumVariation::Rust {
-            non_exhaustive: false,
-        })
-        .parse_callbacks(Box::new(CloneDerive))
-        .parse_callbacks(Box::new(renamer))
-        .parse_callbacks(Box::new(CPrefix))
-        .generate()
-        .expect("Unable to generate c -> rust bindings!");
-    let out_path = PathBuf::from_str("./src/cbindings.rs").expect("Project structure incorrect");
-    bindings
-        .write_to_file(out_path)
-        .expect("Couldn't write bindings!");
+    #[cfg(feature = "bindgen")]
+    bindings::generate();
 
     cc::Build::new()
         .file("src/capi/ft_string.c")
@@ -19,6 +19,7 @@ typedef struct ApplicationContext
     NON_NULL EndpointRegisterService endpointRegisterService;
     NON_NULL EndpointUnregisterService endpointUnregisterService;
     NON_NULL EndpointRequestService endpointRequestService;
+    NON_NULL EventTriggerBlockingService eventTriggerBlockingService;
 } ApplicationContext;
 
 typedef struct
@@ -32,12 +33,30 @@ typedef struct
 {
     String name;
     String version;
+    // Names of required plugins, optionally followed by a semver version requirement like "storage@^1.2" or "auth>=0.3,<0.5".
+    // A "?" prefix marks an optional dependency like "?metrics@^1", an "after:" or "before:" prefix an ordering hint like "after:logger".
     List_String dependencies;
     EventHandlerFP initHandler;
+    // The api version the plugin requires at least. The loader accepts plugins between its oldest supported version and API_VERSION.
     ApiVersion apiVersion;
 } PluginInfo;
 
+// Describes a plugin beyond what is needed to load it. Empty Strings are treated as not set.
+typedef struct
+{
+    String description;
+    List_String authors;
+    String license;
+    String homepage;
+    // Free-form metadata as a JSON value.
+    String metadata;
+} PluginMetadata;
+
 extern const ApiVersion API_VERSION;
 NON_NULL PluginInfo plugin_main(Uuid);
+// Optional. Called before the plugin is unloaded at runtime, after its events, endpoints and handlers were removed.
+void plugin_unload(void);
+// Optional. Called after plugin_main to describe the plugin.
+PluginMetadata plugin_metadata(void);
 
 #endif
\ No newline at end of file
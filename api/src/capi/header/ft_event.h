@@ -10,6 +10,14 @@
 // Returns the success state of the registration.
 typedef ServiceError (*EventHandlerFP)(NON_NULL ContextSupplier, String);
 
+// How the handlers of an event are scheduled.
+// DISPATCH_SEQUENTIAL runs the handlers of a trigger one after another in a single job in the order of their priority.
+// DISPATCH_PARALLEL runs every handler in a job of its own, so that slow handlers don't delay the others.
+typedef enum {
+    DISPATCH_SEQUENTIAL = 0,
+    DISPATCH_PARALLEL
+} EventDispatch;
+
 // Handler struct that carries the success state and the generated handler_id with it.
 // The handler_id is required to unregister the handler later.
 // All fields values are undefined unless the error field == SERVICE_SUCCESS.
@@ -24,8 +32,9 @@ typedef struct
 // The first    argument is the handler to be registered.
 // The second   argument has to be the plugins uuid.
 // The third    argument is the events name to register the handler to. The name follows the format "<plugin-name>:<event-name>"
+// The fourth   argument is the priority of the handler. Handlers with a higher priority run first.
 // Returns the success state of the registration.
-typedef EventHandler (*EventHandlerRegisterService)(NON_NULL EventHandlerFP, Uuid, String);
+typedef EventHandler (*EventHandlerRegisterService)(NON_NULL EventHandlerFP, Uuid, String, i32);
 
 // Service function to unregister a handler for a given event.
 // The first    argument is the handlerId to be removed.
@@ -38,8 +47,11 @@ typedef ServiceError (*EventHandlerUnregisterService)(Uuid, Uuid, String);
 // The first    argument is the json schema the events arguments have to satisfy.
 // The second   argument has to be the plugins uuid.
 // The third    argument is the events name. This will be prefixed by this plugins name. It can't contain any ':' characters.
+// The fourth   argument is the dispatch of the event.
+// The fifth    argument is the number of jobs of the event that may run at the same time, where a job is a single handler
+//              with DISPATCH_PARALLEL and all handlers of one trigger with DISPATCH_SEQUENTIAL. 0 doesn't limit them.
 // Returns the success state of the registration.
-typedef ServiceError (*EventRegisterService)(String, Uuid, String);
+typedef ServiceError (*EventRegisterService)(String, Uuid, String, EventDispatch, usize);
 
 // Service function to unregister an event.
 // The first    argument has to be the plugins uuid.
@@ -49,6 +61,9 @@ typedef ServiceError (*EventUnregisterService)(Uuid, String);
 
 // Service function to trigger an event.
 // Events are triggered sequentially but don't block the triggering thread.
+// The handlers run one after another in descending priority and handlers of the same priority in the init order of their plugins.
+// Events registered with DISPATCH_PARALLEL run every handler in a job of their own instead.
+// The endpoints "core:handler_priority" and "core:event_dispatch" change the priority of a handler and the dispatch of an event later on.
 // Success is returned as soon as the event is scheduled successfully.
 // The first    argument has to be the plugins uuid.
 // The second   argument is the events name to be triggered.
@@ -56,4 +71,15 @@ typedef ServiceError (*EventUnregisterService)(Uuid, String);
 // Returns the success state of the trigger.
 typedef ServiceError (*EventTriggerService)(Uuid, String, String);
 
+// Service function to trigger an event and wait for its handlers.
+// The handlers are scheduled like the ones of EventTriggerService, but the call returns once all of them returned or the timeout passed.
+// Handlers that didn't return in time keep running in the background.
+// A WebAssembly plugin can't wait for its own handlers and gets SERVICE_UNAUTHORIZED instead.
+// The first    argument has to be the plugins uuid.
+// The second   argument is the events name to be triggered.
+// The third    argument is the events arguments.
+// The fourth   argument is the timeout in milliseconds. 0 waits until all handlers returned.
+// Returns a json object with a "results" array holding the "plugin", "status" and "error" of every handler in the order the handlers run.
+typedef String (*EventTriggerBlockingService)(Uuid, String, String, u64);
+
 #endif
\ No newline at end of file
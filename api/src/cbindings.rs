@@ -431,6 +431,12 @@ unsafe extern "C" {
 pub type CEventHandlerFP = ::core::option::Option<
     unsafe extern "C" fn(arg1: CContextSupplier, arg2: CString) -> CServiceError,
 >;
+#[repr(u32)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+pub enum CEventDispatch {
+    Sequential = 0,
+    Parallel = 1,
+}
 #[repr(C)]
 #[derive(Debug)]
 pub struct CEventHandler {
@@ -450,19 +456,33 @@ const _: () = {
         [::core::mem::offset_of!(CEventHandler, error) - 24usize];
 };
 pub type CEventHandlerRegisterService = ::core::option::Option<
-    unsafe extern "C" fn(arg1: CEventHandlerFP, arg2: CUuid, arg3: CString) -> CEventHandler,
+    unsafe extern "C" fn(
+        arg1: CEventHandlerFP,
+        arg2: CUuid,
+        arg3: CString,
+        arg4: Ci32,
+    ) -> CEventHandler,
 >;
 pub type CEventHandlerUnregisterService = ::core::option::Option<
     unsafe extern "C" fn(arg1: CUuid, arg2: CUuid, arg3: CString) -> CServiceError,
 >;
 pub type CEventRegisterService = ::core::option::Option<
-    unsafe extern "C" fn(arg1: CString, arg2: CUuid, arg3: CString) -> CServiceError,
+    unsafe extern "C" fn(
+        arg1: CString,
+        arg2: CUuid,
+        arg3: CString,
+        arg4: CEventDispatch,
+        arg5: Cusize,
+    ) -> CServiceError,
 >;
 pub type CEventUnregisterService =
     ::core::option::Option<unsafe extern "C" fn(arg1: CUuid, arg2: CString) -> CServiceError>;
 pub type CEventTriggerService = ::core::option::Option<
     unsafe extern "C" fn(arg1: CUuid, arg2: CString, arg3: CString) -> CServiceError,
 >;
+pub type CEventTriggerBlockingService = ::core::option::Option<
+    unsafe extern "C" fn(arg1: CUuid, arg2: CString, arg3: CString, arg4: Cu64) -> CString,
+>;
 pub type CRequestHandlerFP = ::core::option::Option<
     unsafe extern "C" fn(arg1: CContextSupplier, arg2: CString, arg3: CString) -> CString,
 >;
@@ -509,10 +529,11 @@ pub struct CApplicationContext {
     pub endpointRegisterService: CEndpointRegisterService,
     pub endpointUnregisterService: CEndpointUnregisterService,
     pub endpointRequestService: CEndpointRequestService,
+    pub eventTriggerBlockingService: CEventTriggerBlockingService,
 }
 #[allow(clippy::unnecessary_operation, clippy::identity_op)]
 const _: () = {
-    ["Size of CApplicationContext"][::core::mem::size_of::<CApplicationContext>() - 64usize];
+    ["Size of CApplicationContext"][::core::mem::size_of::<CApplicationContext>() - 72usize];
     ["Alignment of CApplicationContext"][::core::mem::align_of::<CApplicationContext>() - 8usize];
     ["Offset of field: CApplicationContext::handlerRegisterService"]
         [::core::mem::offset_of!(CApplicationContext, handlerRegisterService) - 0usize];
@@ -530,6 +551,8 @@ const _: () = {
         [::core::mem::offset_of!(CApplicationContext, endpointUnregisterService) - 48usize];
     ["Offset of field: CApplicationContext::endpointRequestService"]
         [::core::mem::offset_of!(CApplicationContext, endpointRequestService) - 56usize];
+    ["Offset of field: CApplicationContext::eventTriggerBlockingService"]
+        [::core::mem::offset_of!(CApplicationContext, eventTriggerBlockingService) - 64usize];
 };
 #[repr(C)]
 #[derive(Debug, Clone)]
@@ -570,11 +593,41 @@ const _: () = {
     ["Offset of field: CPluginInfo::apiVersion"]
         [::core::mem::offset_of!(CPluginInfo, apiVersion) - 80usize];
 };
+#[repr(C)]
+#[derive(Debug)]
+pub struct CPluginMetadata {
+    pub description: CString,
+    pub authors: CList_String,
+    pub license: CString,
+    pub homepage: CString,
+    pub metadata: CString,
+}
+#[allow(clippy::unnecessary_operation, clippy::identity_op)]
+const _: () = {
+    ["Size of CPluginMetadata"][::core::mem::size_of::<CPluginMetadata>() - 120usize];
+    ["Alignment of CPluginMetadata"][::core::mem::align_of::<CPluginMetadata>() - 8usize];
+    ["Offset of field: CPluginMetadata::description"]
+        [::core::mem::offset_of!(CPluginMetadata, description) - 0usize];
+    ["Offset of field: CPluginMetadata::authors"]
+        [::core::mem::offset_of!(CPluginMetadata, authors) - 24usize];
+    ["Offset of field: CPluginMetadata::license"]
+        [::core::mem::offset_of!(CPluginMetadata, license) - 48usize];
+    ["Offset of field: CPluginMetadata::homepage"]
+        [::core::mem::offset_of!(CPluginMetadata, homepage) - 72usize];
+    ["Offset of field: CPluginMetadata::metadata"]
+        [::core::mem::offset_of!(CPluginMetadata, metadata) - 96usize];
+};
 unsafe extern "C" {
     pub static API_VERSION: CApiVersion;
 }
 unsafe extern "C" {
     pub fn plugin_main(arg1: CUuid) -> CPluginInfo;
 }
+unsafe extern "C" {
+    pub fn plugin_unload();
+}
+unsafe extern "C" {
+    pub fn plugin_metadata() -> CPluginMetadata;
+}
 pub type C__int128_t = i128;
 pub type C__uint128_t = u128;
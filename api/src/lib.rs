@@ -6,6 +6,9 @@
 
 extern crate alloc;
 
+#[cfg(feature = "std")]
+extern crate std;
+
 /// cbindgen:ignore
 #[cfg(not(feature = "unsafe"))]
 #[allow(non_camel_case_types, non_upper_case_globals,
@@ -24,7 +27,7 @@ mod cbindings;
 
 /// cbindgen:ignore
 #[cfg(feature = "unsafe")]
-#[allow(non_camel_case_types, non_upper_case_globals,
+#[allow(non_camel_case_types, non_upper_case_globals, clippy::doc_paragraphs_missing_punctuation,
     non_snake_case, unused, unsafe_op_in_unsafe_fn, 
     clippy::missing_safety_doc, clippy::unreadable_literal, 
     clippy::pub_underscore_fields, clippy::transmute_ptr_to_ptr,
@@ -47,6 +50,7 @@ pub use safe_api::*;
 pub mod misc;
 
 pub use cbindings::CPluginInfo;
+pub use cbindings::CPluginMetadata;
 pub use cbindings::CUuid;
 pub use cbindings::CApiVersion as ApiVersion;
 
@@ -57,4 +61,13 @@ use crate::cbindings::CApiVersion;
 /// 
 #[unsafe(no_mangle)]
 #[used]
-pub static API_VERSION: CApiVersion = CApiVersion::cargo();
\ No newline at end of file
+pub static API_VERSION: CApiVersion = CApiVersion::cargo();
+
+///
+/// The optional functions a plugin may export together with the api version that introduced them.
+/// The loader only calls them for plugins that declare at least that version.
+///
+pub const OPTIONAL_FUNCTIONS: [(&str, CApiVersion); 2] = [
+    ("plugin_unload", CApiVersion { major: 0, feature: 1, patch: 0 }),
+    ("plugin_metadata", CApiVersion { major: 0, feature: 2, patch: 0 }),
+];
\ No newline at end of file
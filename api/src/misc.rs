@@ -6,7 +6,7 @@
 use alloc::vec::Vec;
 use alloc::string::String;
 use alloc::boxed::Box;
-use core::{str::{self, Utf8Error}, slice};
+use core::{fmt, str::{self, Utf8Error}, slice};
 use core::ptr::NonNull;
 
 use derive_more::Display;
@@ -15,7 +15,7 @@ use thiserror::Error;
 #[cfg(feature = "safe")]
 use crate::ServiceError;
 
-use crate::{cbindings::{
+use crate::{OPTIONAL_FUNCTIONS, cbindings::{
     CApiVersion, CList_String, CString, CUuid, asErrorString, createListString, createString, destroyListString, destroyString, emptyListString, fromErrorString, getLengthString, getViewString, isValidListString, isValidString
 }};
 
@@ -74,6 +74,10 @@ impl CString {
         // Calling getLengthString with a valid CString as checked above is safe.
         // The Length value can be trusted as we checked for an invalid string already.
         let len = unsafe { getLengthString(self) };
+        // getViewString returns null for empty views and a slice can't be created from a null pointer.
+        if len == 0 {
+            return Ok("");
+        }
         // SAFETY:
         // Calling getViewString with a valid CString, 0 and it's reported length is safe 
         // as it get's a slice over the entire CString.
@@ -192,7 +196,8 @@ impl CList_String {
         }
         let isize_len = self.length.try_into().map_err(|_error| ApiMiscError::InvalidList)?;
 
-        if self.data.wrapping_offset(isize_len) > self.data {
+        // The end of the list only lies before its start if the offset wrapped around the address space.
+        if self.data.wrapping_offset(isize_len) < self.data {
             return Err(ApiMiscError::InvalidList);
         }
         // SAFETY:
@@ -249,6 +254,13 @@ impl PartialEq for CApiVersion {
     }
 }
 
+impl fmt::Display for CApiVersion {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.feature, self.patch)
+    }
+}
+
 impl CApiVersion {
     #[must_use]
     #[inline]
@@ -286,6 +298,18 @@ impl CApiVersion {
         }
     }
 
+    ///
+    /// Whether a plugin declaring this api version may export the optional function.
+    /// Functions that aren't part of `OPTIONAL_FUNCTIONS` are never offered.
+    ///
+    #[must_use]
+    #[inline]
+    pub fn offers(self, function: &str) -> bool {
+        OPTIONAL_FUNCTIONS
+            .iter()
+            .any(|(name, introduced)| *name == function && self.major == introduced.major && self.feature >= introduced.feature)
+    }
+
 }
 
 ///
@@ -367,4 +391,21 @@ unsafe extern "C" fn drop_string(str: *const u8, length: usize) {
     // SAFETY: See safety block above.
     let owned = unsafe { Box::from_raw(string) };
     drop(owned);
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use alloc::vec;
+
+    use crate::cbindings::{CList_String, CString};
+
+    #[test]
+    fn empty_string_is_empty_str() {
+        assert_eq!(CString::from("").as_str().ok(), Some(""), "an empty CString has no view but is still valid");
+    }
+
+    #[test]
+    fn string_list_as_array() {
+        let list = CList_String::from(vec![CString::from("storage"), CString::from("?metrics")]);
+        assert_eq!(list.as_array().ok(), Some(vec!["storage", "?metrics"]), "every list without an overflow is accepted");
+    }
+}
@@ -16,15 +16,21 @@ use core::{
     any,
     fmt::Debug,
     hash::{Hash, Hasher},
+    num::NonZeroUsize,
+    time::Duration,
 };
+#[cfg(feature = "std")]
+use core::panic::AssertUnwindSafe;
+#[cfg(feature = "std")]
+use std::panic;
 
 use derive_more::Display;
 use thiserror::Error;
 use uuid::Uuid;
 
 use crate::{CUuid, cbindings::{
-    CApiVersion, CApplicationContext, CEventHandler, CList_String, CPluginInfo, CServiceError,
-    CString,
+    CApiVersion, CApplicationContext, CEventDispatch, CEventHandler, CList_String, CPluginInfo, CPluginMetadata,
+    CServiceError, CString,
 }};
 use crate::misc::ApiMiscError;
 use crate::safe_api::pointer_traits::{
@@ -36,11 +42,19 @@ use crate::safe_api::pointer_traits::{
     EventHandlerRegisterServiceUnsafeFP, EventHandlerUnregisterService,
     EventHandlerUnregisterServiceFPAdapter as _, EventHandlerUnregisterServiceUnsafeFP,
     EventRegisterService, EventRegisterServiceFPAdapter as _, EventRegisterServiceUnsafeFP,
+    EventTriggerBlockingService, EventTriggerBlockingServiceFPAdapter as _, EventTriggerBlockingServiceUnsafeFP,
     EventTriggerService, EventTriggerServiceFPAdapter as _, EventTriggerServiceUnsafeFP,
     EventUnregisterService, EventUnregisterServiceFPAdapter as _, EventUnregisterServiceUnsafeFP,
     RequestHandlerFunc,
 };
 
+#[doc(hidden)]
+pub use linkme;
+
+const AFTER_PREFIX: &str = "after:";
+const BEFORE_PREFIX: &str = "before:";
+const OPTIONAL_PREFIX: &str = "?";
+
 ///
 /// This trait should be implemented by types where an error state can be converted into a `Result` of some type `T` and an appropriate `ServiceError`.
 ///
@@ -373,6 +387,7 @@ pub struct ApplicationContext {
     event_handler_unregister: EventHandlerUnregisterServiceUnsafeFP,
     event_register: EventRegisterServiceUnsafeFP,
     event_trigger: EventTriggerServiceUnsafeFP,
+    event_trigger_blocking: EventTriggerBlockingServiceUnsafeFP,
     event_unregister: EventUnregisterServiceUnsafeFP,
 }
 
@@ -393,11 +408,28 @@ impl CApplicationContext {
             endpoint_register: self.endpointRegisterService.error(NullFunctionPointer)?,
             endpoint_unregister: self.endpointUnregisterService.error(NullFunctionPointer)?,
             endpoint_request: self.endpointRequestService.error(NullFunctionPointer)?,
+            event_trigger_blocking: self.eventTriggerBlockingService.error(NullFunctionPointer)?,
         })
     }
 }
 
 impl ApplicationContext {
+    ///
+    /// Declares an event handler of the plugin long-running, so that it runs on a thread of its own instead of the event pool.
+    /// Without a `handler_id` the handler of the plugin that is running on the calling thread is declared, which lets a handler
+    /// that is going to block for a long time give its thread of the event pool back.
+    /// # Errors
+    /// Fails if the handler isn't found or the plugin doesn't own it.
+    ///
+    #[inline]
+    pub fn declare_long_running(&self, plugin_id: Uuid, handler_id: Option<Uuid>) -> Result<(), ServiceError> {
+        let args = handler_id.map_or_else(
+            || String::from("{\"handler_id\":null}"),
+            |id| format!("{{\"handler_id\":\"{id}\"}}"),
+        );
+        self.endpoint_request("core:long_running", plugin_id, args).map(drop)
+    }
+
     ///
     /// Makes a request to the endpoint.
     /// An endpoint is a 1 to 1 request with a return value.
@@ -435,6 +467,7 @@ impl ApplicationContext {
         NR: EndpointRegisterService,
         NU: EndpointUnregisterService,
         NT: EndpointRequestService,
+        EB: EventTriggerBlockingService,
     >() -> Self {
         Self {
             event_handler_register: HR::c_register_fp(),
@@ -445,6 +478,7 @@ impl ApplicationContext {
             endpoint_register: NR::c_register_fp(),
             endpoint_unregister: NU::c_unregister_fp(),
             endpoint_request: NT::c_request_fp(),
+            event_trigger_blocking: EB::c_trigger_blocking_fp(),
         }
     }
 
@@ -464,6 +498,7 @@ impl ApplicationContext {
         endpoint_register_service: EndpointRegisterServiceUnsafeFP,
         endpoint_unregister_service: EndpointUnregisterServiceUnsafeFP,
         endpoint_request_service: EndpointRequestServiceUnsafeFP,
+        event_trigger_blocking_service: EventTriggerBlockingServiceUnsafeFP,
     ) -> Self {
         Self {
             event_handler_register: handler_register_service,
@@ -474,6 +509,7 @@ impl ApplicationContext {
             endpoint_register: endpoint_register_service,
             endpoint_unregister: endpoint_unregister_service,
             endpoint_request: endpoint_request_service,
+            event_trigger_blocking: event_trigger_blocking_service,
         }
     }
 
@@ -510,7 +546,7 @@ impl ApplicationContext {
     }
 
     ///
-    /// Registers a new event.
+    /// Registers a new event with the sequential dispatch.
     /// An event is a 1 to many broadcast without a return value.
     /// The function takes an `args_schema` a `json_schema` describing the valid arguments for the event,
     /// a `plugin_id` that owns the event, and the name of the new event. The event name will be prefixed by this plugins name.
@@ -526,14 +562,14 @@ impl ApplicationContext {
         plugin_id: Uuid,
         event_name: T,
     ) -> Result<(), ServiceError> {
-        self.event_register.to_safe_fp()(args_schema, plugin_id, event_name)
+        self.event_register.to_safe_fp()(args_schema, plugin_id, event_name, EventDispatch::Sequential, None)
     }
 
     ///
-    /// Registers a new `EventHandler` to a given event.
+    /// Registers a new `EventHandler` with the priority 0 to a given event.
     /// The function takes an `EventHandlerFunc` generic parameter, the registering plugins id and the name of the event.
     /// On success a new `EventHandler` instance is returned which can be used to unregister the `EventHandler` later.
-    /// The name follows the format "<plugin-name>:<event-name>"
+    /// The name follows the format "<plugin-name>:<event-name>".
     /// # Errors
     /// One reason the registration might fail is that the `handler_id` was already
     /// registered in which case the old value stays unchanged and the new registration fails.
@@ -544,7 +580,43 @@ impl ApplicationContext {
         plugin_id: Uuid,
         event_name: T,
     ) -> Result<EventHandler, ServiceError> {
-        self.event_handler_register.to_safe_fp::<E, _>()(plugin_id, event_name)
+        self.event_handler_register.to_safe_fp::<E, _>()(plugin_id, event_name, 0)
+    }
+
+    ///
+    /// Registers a new `EventHandler` with the given priority to a given event like `register_event_handler`.
+    /// Handlers with a higher priority run first, handlers of the same priority run in the init order of their plugins.
+    /// # Errors
+    /// Fails for the same reasons as `register_event_handler`.
+    ///
+    #[inline]
+    pub fn register_event_handler_with_priority<E: EventHandlerFunc>(
+        &self,
+        plugin_id: Uuid,
+        event_name: &str,
+        priority: i32,
+    ) -> Result<EventHandler, ServiceError> {
+        self.event_handler_register.to_safe_fp::<E, _>()(plugin_id, event_name, priority)
+    }
+
+    ///
+    /// Registers a new event like `register_event` with the given scheduling of its handlers.
+    /// With `max_concurrency` at most that many jobs of the event run at the same time, where a job is a single handler
+    /// in the parallel mode and all handlers of one trigger in the sequential mode. Events registered without a dispatch
+    /// run their handlers sequentially without a limit.
+    /// # Errors
+    /// Fails for the same reasons as `register_event`.
+    ///
+    #[inline]
+    pub fn register_event_with_dispatch(
+        &self,
+        args_schema: &str,
+        plugin_id: Uuid,
+        event_name: &str,
+        dispatch: EventDispatch,
+        max_concurrency: Option<NonZeroUsize>,
+    ) -> Result<(), ServiceError> {
+        self.event_register.to_safe_fp()(args_schema, plugin_id, event_name, dispatch, max_concurrency)
     }
 
     ///
@@ -562,12 +634,15 @@ impl ApplicationContext {
             endpointRegisterService: Some(self.endpoint_register),
             endpointUnregisterService: Some(self.endpoint_unregister),
             endpointRequestService: Some(self.endpoint_request),
+            eventTriggerBlockingService: Some(self.event_trigger_blocking),
         }
     }
 
     ///
     /// Triggers an event.
     /// An event is a 1 to many broadcast without a return value.
+    /// The handlers run one after another in descending priority and handlers of the same priority in the init order of their plugins,
+    /// unless the event was registered with the parallel dispatch (see `register_event_with_dispatch`).
     /// To trigger an event the caller must be the owner of the event.
     /// This will be checked using the `plugin_id`. The full `event_name` in the format "<plugin-name>:<event-name>"
     /// and valid arguments for the event must be provided.
@@ -586,9 +661,32 @@ impl ApplicationContext {
         self.event_trigger.to_safe_fp()(plugin_id, event_name, args)
     }
 
+    ///
+    /// Triggers an event like `trigger_event`, but waits until all handlers returned or the optional timeout passed.
+    /// The handlers are scheduled like the ones of `trigger_event`, so priorities, the dispatch of the event and long-running handlers apply.
+    /// Handlers that didn't return before the timeout keep running in the background.
+    /// The result is a JSON object with a "results" array holding an entry for every handler in the order the handlers run.
+    /// Every entry holds the name of the "plugin" owning the handler, its "status" and the name of the `ServiceError` as "error" if it failed.
+    /// The status is either "finished", "failed", "pending" if the handler didn't return before the timeout or "skipped" if its plugin failed.
+    /// # Errors
+    /// Fails for the same reasons as `trigger_event`. A WebAssembly plugin waiting for its own handlers would never see them return
+    /// and gets `ServiceError::Unauthorized` instead.
+    ///
+    #[inline]
+    pub fn trigger_event_blocking<S: Into<CString>, T: Into<CString>>(
+        &self,
+        plugin_id: Uuid,
+        event_name: S,
+        args: T,
+        timeout: Option<Duration>,
+    ) -> Result<String, ServiceError> {
+        self.event_trigger_blocking.to_safe_fp()(plugin_id, event_name, args, timeout)
+    }
+
     ///
     /// Unregisters an endpoint given the `plugin_id` the endpoint was registered for and the name of the endpoint.
     /// The `endpoint_name` must be the full endpoint name including the plugin prefix.
+    /// The unregistration triggers the "core:unregister" event to inform other plugins about the removed endpoint.
     /// # Errors
     /// The unregistration might fail because no endpoint with such name was found
     /// or the given `plugin_id` wasn't used when registering the endpoint.
@@ -605,6 +703,8 @@ impl ApplicationContext {
     ///
     /// Unregisters an event given the `plugin_id` the event was registered for and the name of the event.
     /// The `event_name` must be the full event name including the plugin prefix.
+    /// The unregistration triggers the "core:unregister" event to inform other plugins about the removed event.
+    /// All events, endpoints and handlers of a plugin are removed the same way when it is unloaded, fails or the loader shuts down.
     /// # Errors
     /// The unregistration might fail because no event with such name was found
     /// or the given `plugin_id` wasn't used when registering the event.
@@ -620,7 +720,7 @@ impl ApplicationContext {
 
     ///
     /// Unregisters an `EventHandler` given its `handler_id`, the `plugin_id` which registered the handler and the events name which the handler is registered for.
-    /// The name follows the format "<plugin-name>:<event-name>"
+    /// The name follows the format "<plugin-name>:<event-name>".
     /// # Errors
     /// The unregistration might fail because no handler with the id can be found
     /// or the given `plugin_id` wasn't used when registering the handler.
@@ -657,7 +757,7 @@ pub struct PluginInfo {
 
 impl CPluginInfo {
     ///
-    /// Converts a `CPluginInfo` to the equivalent `PluginInfo`
+    /// Converts a `CPluginInfo` to the equivalent `PluginInfo`.
     /// # Errors
     /// The conversion might fail when the function pointer of the init function is invalid.
     ///
@@ -675,7 +775,7 @@ impl CPluginInfo {
 
 impl PluginInfo {
     ///
-    /// A getter for the api version a plugin is using.
+    /// A getter for the api version a plugin requires at least.
     ///
     #[must_use]
     #[inline]
@@ -684,7 +784,11 @@ impl PluginInfo {
     }
 
     ///
-    /// A getter for the dependencies of a plugin.
+    /// A getter for all declared dependencies of a plugin.
+    /// A dependency is the name of another plugin optionally followed by a semver version requirement
+    /// the other plugins version has to satisfy like `"storage@^1.2"` or `"auth>=0.3,<0.5"`.
+    /// A `?` prefix marks an optional dependency (`"?metrics@^1"`),
+    /// an `after:` or `before:` prefix a pure ordering hint (`"after:logger"`).
     /// # Errors
     /// Getting the dependencies might fail if the strings or the list itself is not valid.
     ///
@@ -713,6 +817,26 @@ impl PluginInfo {
         self.init_handler
     }
 
+    ///
+    /// A getter for the names of the plugins this plugin is initialized after if they are loaded.
+    /// # Errors
+    /// Getting the hints might fail if the strings or the list itself is not valid.
+    ///
+    #[inline]
+    pub fn load_after(&self) -> Result<Vec<&str>, ApiMiscError> {
+        self.prefixed_dependencies(AFTER_PREFIX)
+    }
+
+    ///
+    /// A getter for the names of the plugins this plugin is initialized before if they are loaded.
+    /// # Errors
+    /// Getting the hints might fail if the strings or the list itself is not valid.
+    ///
+    #[inline]
+    pub fn load_before(&self) -> Result<Vec<&str>, ApiMiscError> {
+        self.prefixed_dependencies(BEFORE_PREFIX)
+    }
+
     ///
     /// A getter function for the name of a plugin.
     /// # Errors
@@ -725,7 +849,7 @@ impl PluginInfo {
 
     ///
     /// Creates a new `PluginInfo` from the init function as a generic parameter, the name of the plugin,
-    /// the version of the plugin, its dependencies and the api version the plugin was compiled with.
+    /// the version of the plugin, its dependencies and the api version the plugin requires at least, usually `API_VERSION`.
     ///
     #[inline]
     pub fn new<E: EventHandlerFunc, N: Into<CString>, V: Into<CString>, D: Into<CList_String>>(
@@ -745,7 +869,7 @@ impl PluginInfo {
 
     ///
     /// Creates a new `PluginInfo` from the name of the plugin, the version of the plugin, its dependencies
-    /// the init function, and the api version the plugin was compiled with.
+    /// the init function, and the api version the plugin requires at least, usually `API_VERSION`.
     /// # Safe
     /// This creation is safe as long as the function pointer implementation is following the C-api correctly.
     ///
@@ -767,7 +891,43 @@ impl PluginInfo {
     }
 
     ///
-    /// Converts an `PluginInfo` to the equivalent `CPluginInfo`
+    /// A getter for the optional dependencies of a plugin with their version requirements.
+    /// # Errors
+    /// Getting the dependencies might fail if the strings or the list itself is not valid.
+    ///
+    #[inline]
+    pub fn optional_dependencies(&self) -> Result<Vec<&str>, ApiMiscError> {
+        self.prefixed_dependencies(OPTIONAL_PREFIX)
+    }
+
+    fn prefixed_dependencies(&self, prefix: &str) -> Result<Vec<&str>, ApiMiscError> {
+        Ok(self
+            .dependencies()?
+            .into_iter()
+            .filter_map(|dependency| dependency.trim().strip_prefix(prefix))
+            .map(str::trim_start)
+            .collect())
+    }
+
+    ///
+    /// A getter for the dependencies a plugin requires with their version requirements.
+    /// # Errors
+    /// Getting the dependencies might fail if the strings or the list itself is not valid.
+    ///
+    #[inline]
+    pub fn required_dependencies(&self) -> Result<Vec<&str>, ApiMiscError> {
+        Ok(self
+            .dependencies()?
+            .into_iter()
+            .map(str::trim)
+            .filter(|dependency| {
+                ![OPTIONAL_PREFIX, AFTER_PREFIX, BEFORE_PREFIX].iter().any(|prefix| dependency.starts_with(prefix))
+            })
+            .collect())
+    }
+
+    ///
+    /// Converts an `PluginInfo` to the equivalent `CPluginInfo`.
     ///
     #[must_use]
     #[inline]
@@ -806,6 +966,230 @@ impl From<CPluginInfo> for Result<PluginInfo, ServiceError> {
     }
 }
 
+///
+/// The `PluginMetadata` struct describes a plugin beyond what is needed to load it.
+/// It is returned from the optional `plugin_metadata` function of a plugin. Empty strings are treated as not set.
+///
+pub struct PluginMetadata {
+    authors: CList_String,
+    description: CString,
+    homepage: CString,
+    license: CString,
+    metadata: CString,
+}
+
+impl CPluginMetadata {
+    ///
+    /// Converts a `CPluginMetadata` to the equivalent `PluginMetadata`.
+    ///
+    #[must_use]
+    #[inline]
+    pub fn to_rust(self) -> PluginMetadata {
+        PluginMetadata {
+            authors: self.authors,
+            description: self.description,
+            homepage: self.homepage,
+            license: self.license,
+            metadata: self.metadata,
+        }
+    }
+}
+
+impl PluginMetadata {
+    ///
+    /// A getter for the authors of a plugin.
+    /// # Errors
+    /// Getting the authors might fail if the strings or the list itself is not valid.
+    ///
+    #[inline]
+    pub fn authors(&self) -> Result<Vec<&str>, ApiMiscError> {
+        self.authors.as_array()
+    }
+
+    ///
+    /// A getter for the description of a plugin.
+    /// # Errors
+    /// Getting the description might fail if the string is not valid.
+    ///
+    #[inline]
+    pub fn description(&self) -> Result<&str, ApiMiscError> {
+        self.description.as_str()
+    }
+
+    ///
+    /// A getter for the homepage of a plugin.
+    /// # Errors
+    /// Getting the homepage might fail if the string is not valid.
+    ///
+    #[inline]
+    pub fn homepage(&self) -> Result<&str, ApiMiscError> {
+        self.homepage.as_str()
+    }
+
+    ///
+    /// A getter for the license of a plugin.
+    /// # Errors
+    /// Getting the license might fail if the string is not valid.
+    ///
+    #[inline]
+    pub fn license(&self) -> Result<&str, ApiMiscError> {
+        self.license.as_str()
+    }
+
+    ///
+    /// A getter for the free-form metadata of a plugin as a JSON value.
+    /// # Errors
+    /// Getting the metadata might fail if the string is not valid.
+    ///
+    #[inline]
+    pub fn metadata(&self) -> Result<&str, ApiMiscError> {
+        self.metadata.as_str()
+    }
+
+    ///
+    /// Creates a new `PluginMetadata` from the description, the authors, the license and the homepage of the plugin
+    /// and free-form metadata as a JSON value. Empty strings are treated as not set.
+    ///
+    #[inline]
+    pub fn new<D: Into<CString>, A: Into<CList_String>, L: Into<CString>, H: Into<CString>, M: Into<CString>>(
+        description: D,
+        authors: A,
+        license: L,
+        homepage: H,
+        metadata: M,
+    ) -> Self {
+        Self {
+            authors: authors.into(),
+            description: description.into(),
+            homepage: homepage.into(),
+            license: license.into(),
+            metadata: metadata.into(),
+        }
+    }
+
+    ///
+    /// Converts a `PluginMetadata` to the equivalent `CPluginMetadata`.
+    ///
+    #[must_use]
+    #[inline]
+    pub fn to_c(self) -> CPluginMetadata {
+        CPluginMetadata {
+            description: self.description,
+            authors: self.authors,
+            license: self.license,
+            homepage: self.homepage,
+            metadata: self.metadata,
+        }
+    }
+}
+
+impl From<PluginMetadata> for CPluginMetadata {
+    #[inline]
+    fn from(value: PluginMetadata) -> Self {
+        value.to_c()
+    }
+}
+
+impl From<CPluginMetadata> for PluginMetadata {
+    #[inline]
+    fn from(value: CPluginMetadata) -> Self {
+        value.to_rust()
+    }
+}
+
+///
+/// The entrypoints of a plugin that is linked into the loader binary instead of being loaded from a library.
+/// Instances are collected in `STATIC_PLUGINS` at link time by the `static_plugin` macro.
+///
+#[derive(Clone, Copy)]
+pub struct StaticPlugin {
+    main: unsafe extern "C" fn(CUuid) -> CPluginInfo,
+    metadata: Option<unsafe extern "C" fn() -> CPluginMetadata>,
+    name: &'static str,
+}
+
+impl StaticPlugin {
+    ///
+    /// The `plugin_main` function of the plugin.
+    ///
+    #[must_use]
+    #[inline]
+    pub const fn main(&self) -> unsafe extern "C" fn(CUuid) -> CPluginInfo {
+        self.main
+    }
+
+    ///
+    /// The optional `plugin_metadata` function of the plugin.
+    ///
+    #[must_use]
+    #[inline]
+    pub const fn metadata(&self) -> Option<unsafe extern "C" fn() -> CPluginMetadata> {
+        self.metadata
+    }
+
+    ///
+    /// The name the plugin was registered with. It tells static plugins apart before their `plugin_main` is called
+    /// and has to match the name `plugin_main` returns.
+    ///
+    #[must_use]
+    #[inline]
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+
+    ///
+    /// Creates a new `StaticPlugin` from the entrypoints of a plugin and its name.
+    /// Use the `static_plugin` macro instead of calling this directly.
+    ///
+    #[must_use]
+    #[inline]
+    pub const fn new(
+        main: unsafe extern "C" fn(CUuid) -> CPluginInfo,
+        metadata: Option<unsafe extern "C" fn() -> CPluginMetadata>,
+        name: &'static str,
+    ) -> Self {
+        Self { main, metadata, name }
+    }
+}
+
+///
+/// All plugins linked into the current binary with the `static_plugin` macro.
+///
+#[linkme::distributed_slice]
+pub static STATIC_PLUGINS: [StaticPlugin];
+
+///
+/// Decides how the handlers of an event are scheduled on the event pool of the loader.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum EventDispatch {
+    /// Every handler runs in a job of its own, so that slow handlers don't delay the others.
+    Parallel,
+    /// The handlers of a trigger run one after another in a single job in the order of their priority.
+    Sequential,
+}
+
+impl From<CEventDispatch> for EventDispatch {
+    #[inline]
+    fn from(value: CEventDispatch) -> Self {
+        match value {
+            CEventDispatch::Parallel => Self::Parallel,
+            CEventDispatch::Sequential => Self::Sequential,
+        }
+    }
+}
+
+impl From<EventDispatch> for CEventDispatch {
+    #[inline]
+    fn from(value: EventDispatch) -> Self {
+        match value {
+            EventDispatch::Parallel => Self::Parallel,
+            EventDispatch::Sequential => Self::Sequential,
+        }
+    }
+}
+
 ///
 /// `ServiceError` represents all errors that can be reported from
 /// all calls through the C-api.
@@ -875,3 +1259,26 @@ pub enum ServiceError {
     ///
     Unauthorized,
 }
+
+///
+/// Calls the given plugin code and turns a panic unwinding out of it into a `ServiceError::PluginInternalError`.
+///
+/// A panic unwinding out of an `extern "C"` function aborts the whole process, therefore the adapters of
+/// `EventHandlerFunc` and `RequestHandlerFunc` call the handler through this function.
+/// Panics can only be caught with the `std` feature. Without it `call` is called directly and a panic aborts the loader,
+/// because the loader can't catch a panic unwinding out of a plugin. Plugins without the feature can be run isolated instead.
+/// # Errors
+/// Returns the error of `call` or `ServiceError::PluginInternalError` if it panicked.
+///
+#[inline]
+pub fn catch_panic<T, F: FnOnce() -> Result<T, ServiceError>>(call: F) -> Result<T, ServiceError> {
+    #[cfg(feature = "std")]
+    {
+        panic::catch_unwind(AssertUnwindSafe(call)).unwrap_or(Err(ServiceError::PluginInternalError))
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        call()
+    }
+}
+
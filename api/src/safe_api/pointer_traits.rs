@@ -12,14 +12,15 @@
 
 use alloc::borrow::Cow;
 use alloc::string::String;
+use core::{num::NonZeroUsize, time::Duration};
 
 use uuid::Uuid;
 
 use crate::{
     ErrorMapper as _, cbindings::{
-        CApplicationContext, CContextSupplier, CEventHandler, CEventHandlerFP,
+        CApplicationContext, CContextSupplier, CEventDispatch, CEventHandler, CEventHandlerFP,
         CServiceError, CString, CUuid,
-    }, misc::ToCString as _, safe_api::{ApplicationContext, EventHandler, ServiceError}
+    }, misc::ToCString as _, safe_api::{ApplicationContext, EventDispatch, EventHandler, ServiceError, catch_panic}
 };
 
 pub use proc_macros::*;
@@ -87,7 +88,7 @@ pub trait EventHandlerFunc {
             Err(error) => return error.into()
         };
 
-        Self::handle(context_supplier.to_safe_fp(), args).into()
+        catch_panic(|| Self::handle(context_supplier.to_safe_fp(), args)).into()
     }
     
     ///
@@ -127,6 +128,7 @@ pub trait EventHandlerRegisterService {
         c_handler: CEventHandlerFP,
         plugin_id: CUuid,
         c_event_name: CString,
+        priority: i32,
     ) -> CEventHandler {
         let handler = match c_handler.error(ServiceError::NullFunctionPointer) {
             Ok(handler) => handler,
@@ -136,11 +138,11 @@ pub trait EventHandlerRegisterService {
             Ok(event_name) => event_name,
             Err(error) => return error.into(),
         };
-        Self::register(handler, plugin_id.into(), event_name).into()
+        Self::register(handler, plugin_id.into(), event_name, priority).into()
     }
 
     ///
-    /// Registers a new `EventHandler` to an event.
+    /// Registers a new `EventHandler` to an event. Handlers with a higher `priority` run first.
     /// # Errors
     /// One reason the registration might fail is that the `handler_id` was already
     /// registered in which case the old value stays unchanged and the new registration fails.
@@ -150,15 +152,16 @@ pub trait EventHandlerRegisterService {
         handler: EventHandlerFuncUnsafeFP,
         plugin_id: Uuid,
         event_name: T,
+        priority: i32,
     ) -> Result<EventHandler, ServiceError>;
 
 
     #[fp_adapter]
     fn to_safe_fp<E: EventHandlerFunc, T: Into<CString>>(
         self: EventHandlerRegisterServiceUnsafeFP,
-    ) -> impl Fn(Uuid, T) -> Result<EventHandler, ServiceError> {
-        move | plugin_id, event_name| unsafe {
-            self(Some(E::c_handle_fp()), plugin_id.into(), event_name.into()).into()
+    ) -> impl Fn(Uuid, T, i32) -> Result<EventHandler, ServiceError> {
+        move | plugin_id, event_name, priority| unsafe {
+            self(Some(E::c_handle_fp()), plugin_id.into(), event_name.into(), priority).into()
         }
     }
 }
@@ -222,6 +225,7 @@ pub trait EventHandlerUnregisterService {
 /// Function Traits creates a bridge between unsafe C function pointers, implementations of this trait and safe function pointers.
 /// 
 #[fn_trait]
+#[expect(clippy::type_complexity, reason = "the getter of the signature returns it as a function pointer")]
 pub trait EventRegisterService {
     ///
     /// Registers a new `Event` from C code.
@@ -235,6 +239,8 @@ pub trait EventRegisterService {
         c_event_schema: CString,
         plugin_id: CUuid,
         c_event_name: CString,
+        dispatch: CEventDispatch,
+        max_concurrency: usize,
     ) -> CServiceError {
         let event_schema = match c_event_schema.as_str().error(ServiceError::InvalidString) {
             Ok(event_schema) => event_schema,
@@ -244,11 +250,12 @@ pub trait EventRegisterService {
             Ok(event_name) => event_name,
             Err(error) => return error.into(),
         };
-        Self::register(event_schema, plugin_id.into(), event_name).into()
+        Self::register(event_schema, plugin_id.into(), event_name, dispatch.into(), NonZeroUsize::new(max_concurrency)).into()
     }
 
     ///
-    /// Registers a new `Event`.
+    /// Registers a new `Event` whose handlers are scheduled according to `dispatch`.
+    /// With `max_concurrency` at most that many jobs of the event run at the same time.
     /// # Errors
     /// The registration might fail, when the schema is invalid, the name contains a ':', the plugin is not found,
     /// or the event name was already registered for this plugin.
@@ -258,15 +265,18 @@ pub trait EventRegisterService {
         event_schema: S,
         plugin_id: Uuid,
         event_name: T,
+        dispatch: EventDispatch,
+        max_concurrency: Option<NonZeroUsize>,
     ) -> Result<(), ServiceError>;
 
 
     #[fp_adapter]
     fn to_safe_fp<S: Into<CString>, T: Into<CString>>(
         self: EventRegisterServiceUnsafeFP,
-    ) -> impl Fn(S, Uuid, T) -> Result<(), ServiceError> {
-        move |event_schema, plugin_id, event_name| unsafe {
-            self(event_schema.into(), plugin_id.into(), event_name.into()).into()
+    ) -> impl Fn(S, Uuid, T, EventDispatch, Option<NonZeroUsize>) -> Result<(), ServiceError> {
+        move |event_schema, plugin_id, event_name, dispatch, max_concurrency| unsafe {
+            let max = max_concurrency.map_or(0, NonZeroUsize::get);
+            self(event_schema.into(), plugin_id.into(), event_name.into(), dispatch.into(), max).into()
         }
     }
 }
@@ -368,6 +378,67 @@ pub trait EventTriggerService {
     }
 }
 
+///
+/// `EventTriggerBlockingService` `fn_trait`.
+/// 
+/// # Function Traits
+/// Function Traits creates a bridge between unsafe C function pointers, implementations of this trait and safe function pointers.
+/// 
+#[fn_trait]
+pub trait EventTriggerBlockingService {
+    ///
+    /// Triggers an `Event` from C code and waits for its handlers.
+    /// # Safety
+    /// This adapter method is designed to be called from C code.
+    /// It is safe to call with valid arguments and does the same as [`EventTriggerBlockingService::trigger_blocking`].
+    ///
+    #[adapter]
+    #[inline]
+    unsafe extern "C" fn c_trigger_blocking(
+        plugin_id: CUuid,
+        c_event_name: CString,
+        c_args: CString,
+        timeout_ms: u64,
+    ) -> CString {
+        let event_name = match c_event_name.as_str().error(ServiceError::InvalidString) {
+            Ok(event_name) => event_name,
+            Err(error) => return error.into(),
+        };
+        let args = match c_args.as_str().error(ServiceError::InvalidString) {
+            Ok(args) => args,
+            Err(error) => return error.into(),
+        };
+        let timeout = (timeout_ms != 0).then(|| Duration::from_millis(timeout_ms));
+        Self::trigger_blocking(plugin_id.into(), event_name, args, timeout).to_c_string()
+    }
+
+    ///
+    /// Triggers an `Event` and waits until all handlers returned or the `timeout` passed.
+    /// Returns the results of the handlers as JSON.
+    /// # Errors
+    /// The trigger of an event might fail for the same reasons as [`EventTriggerService::trigger`]
+    /// or because a WebAssembly plugin would wait for its own handlers.
+    /// 
+    #[sig]
+    fn trigger_blocking<S: AsRef<str>, T: AsRef<str>>(
+        plugin_id: Uuid,
+        event_name: S,
+        args: T,
+        timeout: Option<Duration>,
+    ) -> Result<String, ServiceError>;
+
+
+    #[fp_adapter]
+    fn to_safe_fp<S: Into<CString>, T: Into<CString>>(
+        self: EventTriggerBlockingServiceUnsafeFP,
+    ) -> impl Fn(Uuid, S, T, Option<Duration>) -> Result<String, ServiceError> {
+        move |plugin_id, event_name, args, timeout| unsafe {
+            let timeout_ms = timeout.map_or(0, |duration| u64::try_from(duration.as_millis()).unwrap_or(u64::MAX).max(1));
+            self(plugin_id.into(), event_name.into(), args.into(), timeout_ms).into()
+        }
+    }
+}
+
 ///
 /// `RequestHandlerFunc` `fn_trait`.
 /// 
@@ -401,7 +472,7 @@ pub trait RequestHandlerFunc {
             Ok(context) => context,
             Err(error) => return error.into(),
         };
-        Self::handle(context_supplier.to_safe_fp(), plugin_name, args).to_c_string()
+        catch_panic(|| Self::handle(context_supplier.to_safe_fp(), plugin_name, args)).to_c_string()
     }
 
     ///
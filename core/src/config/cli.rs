@@ -1,5 +1,5 @@
 use core::str::FromStr;
-use std::collections::HashMap;
+use std::{collections::HashMap, path::{Path, PathBuf}};
 
 use clap::Parser as ClapParser;
 use derive_more::Display;
@@ -16,23 +16,58 @@ use crate::util::ResultFlatten as _;
 #[derive(ClapParser, Debug)]
 #[command(version)]
 pub struct Parser {
+    /// Validates the manifests of all plugins without loading them and exits.
+    #[arg(long)]
+    check_plugins: bool,
+    /// Runs as the host process of the given plugin library. Started by the loader for isolated plugins.
+    #[arg(long, hide = true, requires = "host_socket")]
+    host: Option<PathBuf>,
+    /// The socket the host process connects to.
+    #[arg(long, hide = true)]
+    host_socket: Option<PathBuf>,
+    /// A directory to search for plugins. Can be given multiple times and takes precedence over all other search paths.
+    #[arg(long("plugin-dir"))]
+    plugin_dirs: Vec<PathBuf>,
     #[arg(short, long("plugin"), num_args = 0.., )]
     plugins: Vec<PluginOption>,
 }
 
 pub struct Cli {
+    check_plugins: bool,
+    host: Option<Box<Path>>,
+    host_socket: Option<Box<Path>>,
+    plugin_dirs: Box<[Box<Path>]>,
     plugins: LockedVec<PluginOption>,
 }
 
 impl From<Parser> for Cli {
     fn from(value: Parser) -> Self {
         Self {
+            check_plugins: value.check_plugins,
+            host: value.host.map(Box::from),
+            host_socket: value.host_socket.map(Box::from),
+            plugin_dirs: value.plugin_dirs.into_iter().map(Box::from).collect(),
             plugins: LockedVec::from_pointee(Vector::from(value.plugins)),
         }
     }
 }
 
 impl Cli {
+    pub const fn check_plugins(&self) -> bool {
+        self.check_plugins
+    }
+
+    ///
+    /// The plugin library and the socket of the loader if this process is the host process of an isolated plugin.
+    ///
+    pub fn host(&self) -> Option<(&Path, &Path)> {
+        Some((self.host.as_deref()?, self.host_socket.as_deref()?))
+    }
+
+    pub fn plugin_dirs(&self) -> &[Box<Path>] {
+        &self.plugin_dirs
+    }
+
     pub const fn plugins(&self) -> &LockedVec<PluginOption> {
         &self.plugins
     }
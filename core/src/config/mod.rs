@@ -1,5 +1,6 @@
 
 pub mod cli;
+pub mod settings;
 
 use alloc::{borrow::Cow, sync::Arc};
 use core::str::FromStr as _;
@@ -23,11 +24,21 @@ use toml::Table;
 use toml::de::Error as TomlError;
 
 use crate::{
-    config::cli::{CliError, PluginOption},
+    config::{cli::{CliError, PluginOption}, settings::CoreSettings},
     governor::{GovernorError, get_gov},
     util::{LockedMap, MapExt as _},
 };
 
+///
+/// The environment variable with additional plugin search paths, separated like the `PATH` of the platform.
+///
+pub const PLUGIN_PATH_VAR: &str = "PLUGIN_LOADER_PATH";
+
+///
+/// The config file with the settings of the core.
+///
+const CORE_CONFIG_FILE: &str = "core.toml";
+
 pub type ConfigMap = LockedMap<Box<str>, Table>;
 
 #[derive(Default)]
@@ -60,6 +71,17 @@ impl Config {
                 .map(Box::deref)
     }
     
+    ///
+    /// The settings of the core. Config files are keyed by their full file name, therefore the settings of `config/core.toml`
+    /// are read first and the "core" options of the environment and the command line are merged over them.
+    ///
+    pub fn core_settings(&self) -> Result<CoreSettings, ConfigError> {
+        let configs = self.configs.load();
+        let file = configs.get(CORE_CONFIG_FILE).cloned().unwrap_or_default();
+        let overrides = configs.get("core").cloned().unwrap_or_default();
+        Ok(file.join_merge(overrides, |_, _, override_val| override_val).try_into()?)
+    }
+
     fn env_prefix(&self) -> Result<Box<str>, ConfigError> {
         let dir = self.config_dir()?.file_name().ok_or(ConfigError::NoConfigDir)?;
         let converted = convert_case::Casing::to_case(&dir.to_string_lossy(), Case::Constant);
@@ -89,6 +111,7 @@ impl Config {
     fn parse_env() -> Result<Vec<PluginOption>, ConfigError> {
         let prefix = get_gov()?.config().env_prefix()?;
         Ok(env::vars()
+            .filter(|(key, _)| key != PLUGIN_PATH_VAR)
             .filter_map(|(key, value)| key.strip_prefix(&*prefix).map(|stripped_key| format!("{stripped_key}={value}")))
             .map(|arg| PluginOption::from_str(&arg))
             .collect::<Result<Vec<_>, cli::CliError>>()?)
@@ -0,0 +1,276 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use serde::Deserialize;
+
+const DEFAULT_CALL_TIMEOUT_MS: u64 = 30_000;
+const DEFAULT_INIT_TIMEOUT_MS: u64 = 30_000;
+const DEFAULT_RESPAWN_LIMIT: usize = 3;
+const DEFAULT_SLOW_HANDLER_MS: u64 = 10_000;
+const DEFAULT_WASM_FUEL: u64 = 1_000_000_000;
+
+///
+/// The settings of the loader itself.
+/// They are read from the "core" config like the config of any plugin (e.g. `config/core.toml` or `-p core:<key>=<value>`).
+///
+#[expect(clippy::module_name_repetitions, reason = "the settings are the settings of the core")]
+#[derive(Deserialize, Clone)]
+#[serde(default)]
+pub struct CoreSettings {
+    /// The time in milliseconds the loader waits for the process of an isolated or executable plugin to answer a call.
+    /// Calls of long-running handlers wait without a limit. 0 disables the limit.
+    call_timeout_ms: u64,
+    dependency_policy: DependencyPolicy,
+    /// The plugins that are never loaded, even if they are enabled or part of the `plugin_set`.
+    disabled_plugins: Box<[Box<str>]>,
+    /// The plugins that are loaded in addition to the `plugin_set`. If neither is given, all plugins are loaded.
+    enabled_plugins: Box<[Box<str>]>,
+    /// The number of threads running event handlers. 0 uses one thread per available CPU.
+    event_pool_size: usize,
+    /// The number of panics or crashes of its handlers after which a plugin is quarantined. 0, the default, disables the quarantine.
+    fault_limit: usize,
+    /// The time in milliseconds the start waits for the "core:init" handlers before it publishes the load report anyway.
+    /// Handlers still running keep running. 0 waits without a limit.
+    init_timeout_ms: u64,
+    /// The plugins that are loaded by a host process of their own instead of the loader itself.
+    /// Entries are matched against the name in the manifest of a plugin or the file name of its library. "*" isolates all plugins.
+    isolated_plugins: Box<[Box<str>]>,
+    /// Directories searched for plugins after the `--plugin-dir` arguments, the `PLUGIN_LOADER_PATH` and the plugin folder
+    /// of the config directory, for example the directory of system-wide plugins. Relative paths start at the config directory.
+    plugin_paths: Box<[PathBuf]>,
+    /// The name of the entry of `plugin_sets` whose plugins are loaded, for example "headless" or "full".
+    plugin_set: Option<Box<str>>,
+    /// Named lists of plugins to choose from with `plugin_set`.
+    plugin_sets: HashMap<Box<str>, Box<[Box<str>]>>,
+    /// How often the host process of an isolated plugin is respawned after it crashed. 0 disables respawning.
+    respawn_limit: usize,
+    /// The time in milliseconds after which a running event handler is reported as slow, unless it declared itself long-running.
+    /// 0 disables the reports.
+    slow_handler_ms: u64,
+    /// Aborts the start on the first plugin that fails to load instead of reporting and skipping it.
+    strict_loading: bool,
+    /// Which plugin files are trusted to be loaded at all.
+    trust: TrustPolicy,
+    /// The fuel a WebAssembly plugin gets for every call of the core, roughly the number of instructions it may execute.
+    /// A call running out of fuel fails with `PluginInternalError`. 0 disables the limit.
+    wasm_fuel: u64,
+    /// Reloads plugins when their library in one of the search paths changes and unloads them when it is removed.
+    watch_plugins: bool,
+}
+
+impl Default for CoreSettings {
+    fn default() -> Self {
+        Self {
+            call_timeout_ms: DEFAULT_CALL_TIMEOUT_MS,
+            dependency_policy: DependencyPolicy::default(),
+            disabled_plugins: Box::default(),
+            enabled_plugins: Box::default(),
+            event_pool_size: 0,
+            fault_limit: 0,
+            init_timeout_ms: DEFAULT_INIT_TIMEOUT_MS,
+            isolated_plugins: Box::default(),
+            plugin_paths: Box::default(),
+            plugin_set: None,
+            plugin_sets: HashMap::default(),
+            respawn_limit: DEFAULT_RESPAWN_LIMIT,
+            slow_handler_ms: DEFAULT_SLOW_HANDLER_MS,
+            strict_loading: false,
+            trust: TrustPolicy::default(),
+            wasm_fuel: DEFAULT_WASM_FUEL,
+            watch_plugins: false,
+        }
+    }
+}
+
+impl CoreSettings {
+    pub const fn call_timeout_ms(&self) -> u64 {
+        self.call_timeout_ms
+    }
+
+    pub const fn dependency_policy(&self) -> DependencyPolicy {
+        self.dependency_policy
+    }
+
+    pub const fn event_pool_size(&self) -> usize {
+        self.event_pool_size
+    }
+
+    pub const fn fault_limit(&self) -> usize {
+        self.fault_limit
+    }
+
+    pub const fn init_timeout_ms(&self) -> u64 {
+        self.init_timeout_ms
+    }
+
+    ///
+    /// Whether the plugin with the given manifest name or library file name is loaded at all.
+    /// Returns `None` if the `plugin_set` isn't one of the `plugin_sets`.
+    ///
+    pub fn is_enabled(&self, names: &[&str]) -> Option<bool> {
+        let in_set = match &self.plugin_set {
+            Some(set) => matches_any(self.plugin_sets.get(set)?, names),
+            None => self.enabled_plugins.is_empty(),
+        };
+        let selected = in_set || matches_any(&self.enabled_plugins, names);
+        Some(selected && !matches_any(&self.disabled_plugins, names))
+    }
+
+    ///
+    /// Whether the plugin with the given manifest name or library file name is loaded by a host process of its own.
+    ///
+    pub fn is_isolated(&self, names: &[&str]) -> bool {
+        matches_any(&self.isolated_plugins, names)
+    }
+
+    pub fn plugin_paths(&self) -> &[PathBuf] {
+        &self.plugin_paths
+    }
+
+    pub const fn respawn_limit(&self) -> usize {
+        self.respawn_limit
+    }
+
+    pub const fn slow_handler_ms(&self) -> u64 {
+        self.slow_handler_ms
+    }
+
+    pub const fn strict_loading(&self) -> bool {
+        self.strict_loading
+    }
+
+    pub const fn trust(&self) -> &TrustPolicy {
+        &self.trust
+    }
+
+    pub const fn wasm_fuel(&self) -> u64 {
+        self.wasm_fuel
+    }
+
+    pub const fn watch_plugins(&self) -> bool {
+        self.watch_plugins
+    }
+}
+
+///
+/// Decides what happens with plugins that have missing, unsatisfied or cyclic dependencies.
+///
+#[derive(Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DependencyPolicy {
+    /// Aborts the start of the loader.
+    #[default]
+    Abort,
+    /// Unloads the affected plugins and every plugin depending on them.
+    Skip,
+}
+
+
+///
+/// Decides which plugin files are trusted before any of their code is loaded (e.g. `core:trust={mode="hashes", allowed_hashes=[...]}`).
+/// The `.toml` and `.json` manifests next to the plugin files aren't checked and have to be protected like the config.
+///
+#[derive(Deserialize, Default, Clone)]
+#[serde(default)]
+pub struct TrustPolicy {
+    /// The hex encoded SHA-256 hashes of the plugin files trusted in the "hashes" mode.
+    allowed_hashes: Box<[Box<str>]>,
+    /// Loads plugins without a signature or with an unlisted hash anyway. Plugins with an invalid signature are still rejected.
+    developer_mode: bool,
+    mode: TrustMode,
+    /// The hex encoded ed25519 public keys whose signatures are trusted in the "signatures" mode.
+    public_keys: Box<[Box<str>]>,
+}
+
+impl TrustPolicy {
+    pub fn allowed_hashes(&self) -> &[Box<str>] {
+        &self.allowed_hashes
+    }
+
+    pub const fn developer_mode(&self) -> bool {
+        self.developer_mode
+    }
+
+    pub const fn mode(&self) -> TrustMode {
+        self.mode
+    }
+
+    pub fn public_keys(&self) -> &[Box<str>] {
+        &self.public_keys
+    }
+}
+
+///
+/// How plugin files are verified before they are loaded.
+///
+#[derive(Deserialize, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum TrustMode {
+    /// Only plugin files whose SHA-256 hash is in the `allowed_hashes` are loaded.
+    Hashes,
+    /// Every plugin file is loaded.
+    #[default]
+    None,
+    /// Only plugin files with a detached ed25519 signature by one of the `public_keys` are loaded.
+    /// The signature of `libfoo.so` is the 64 byte file `libfoo.so.sig` next to it.
+    Signatures,
+}
+
+///
+/// Whether any of the names of a plugin is in the list. "*" matches every plugin.
+///
+fn matches_any(list: &[Box<str>], names: &[&str]) -> bool {
+    list.iter().any(|entry| &**entry == "*" || names.contains(&&**entry))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CoreSettings;
+
+    fn enabled(settings: &str, plugins: &[&[&str]]) -> Option<Vec<bool>> {
+        let parsed = toml::from_str::<CoreSettings>(settings).ok()?;
+        plugins.iter().map(|names| parsed.is_enabled(names)).collect()
+    }
+
+    #[test]
+    fn all_plugins_are_enabled_by_default() {
+        assert_eq!(enabled("", &[&["storage"], &["auth", "libauth.so"]]), Some(vec![true, true]), "without a selection every plugin is loaded");
+    }
+
+    #[test]
+    fn enabled_plugins_select_by_any_name() {
+        let settings = r#"enabled_plugins = ["storage", "libauth.so"]"#;
+        assert_eq!(
+            enabled(settings, &[&["storage"], &["auth", "libauth.so"], &["ui"]]),
+            Some(vec![true, true, false]),
+            "a plugin is selected by the name in its manifest or the name of its file"
+        );
+    }
+
+    #[test]
+    fn disabled_plugins_win() {
+        let settings = r#"enabled_plugins = ["*"]
+disabled_plugins = ["auth"]"#;
+        assert_eq!(enabled(settings, &[&["storage"], &["auth"]]), Some(vec![true, false]), "a disabled plugin is never loaded");
+    }
+
+    #[test]
+    fn plugin_sets_are_extended_by_enabled_plugins() {
+        let settings = r#"plugin_set = "headless"
+enabled_plugins = ["metrics"]
+disabled_plugins = ["auth"]
+
+[plugin_sets]
+headless = ["storage", "auth"]
+full = ["storage", "auth", "ui"]"#;
+        assert_eq!(
+            enabled(settings, &[&["storage"], &["metrics"], &["auth"], &["ui"]]),
+            Some(vec![true, true, false, false]),
+            "only the chosen set and the enabled plugins are loaded"
+        );
+    }
+
+    #[test]
+    fn unknown_plugin_sets_are_rejected() {
+        assert_eq!(enabled(r#"plugin_set = "missing""#, &[&["storage"]]), None, "a plugin set that isn't declared can't be chosen");
+    }
+}
@@ -0,0 +1,315 @@
+use alloc::{borrow::Cow, sync::Arc};
+use core::{num::NonZeroUsize, time::Duration};
+use std::{io, path::Path};
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+
+use arc_swap::ArcSwapOption;
+use derive_more::Display;
+use libloading::{Library, Symbol};
+use plugin_loader_api::{
+    ApplicationContext, ErrorMapper as _, EventDispatch, EventHandler, ServiceError,
+    cbindings::{CPluginInfo, CPluginMetadata, CUuid},
+    pointer_traits::{
+        ContextSupplier, EndpointRegisterService, EndpointRequestService, EndpointUnregisterService,
+        EventHandlerFuncUnsafeFP, EventHandlerRegisterService, EventHandlerUnregisterService,
+        EventRegisterService, EventTriggerBlockingService, EventTriggerService, EventUnregisterService,
+        RequestHandlerFuncFPAdapter as _, RequestHandlerFuncUnsafeFP, trait_fn,
+    },
+};
+use serde_json::Value;
+use thiserror::Error;
+use uuid::Uuid;
+
+use crate::{
+    rpc::{
+        self, Connection, HandleParams, MainParams, MainResult, Peer as _, PluginDetails, RegisterEndpointParams,
+        RegisterEventParams, RegisterHandlerParams, RequestEndpointParams, RequestParams,
+        TriggerEventBlockingParams, TriggerEventParams, UnregisterEndpointParams, UnregisterEventParams,
+        UnregisterHandlerParams, method,
+    },
+    util::LockedMap,
+};
+
+static HOST: ArcSwapOption<Host> = ArcSwapOption::const_empty();
+
+///
+/// The state of a host process.
+///
+/// A host process loads the library of a single isolated plugin for a loader running in another process.
+/// It calls the plugin for the loader and forwards the services of the `ApplicationContext` to the loader.
+///
+struct Host {
+    connection: Arc<Connection>,
+    event_handlers: LockedMap<Uuid, EventHandlerFuncUnsafeFP>,
+    library: Library,
+    request_handlers: LockedMap<Uuid, RequestHandlerFuncUnsafeFP>,
+}
+
+#[derive(Debug, Display, Error)]
+pub enum HostError {
+    IO(#[from] io::Error),
+    LibError(#[from] libloading::Error),
+}
+
+///
+/// Runs this process as the host process of the given plugin library until the loader closes the connection.
+///
+#[cfg(unix)]
+#[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+pub fn run(library_path: &Path, socket: &Path) -> Result<(), HostError> {
+    // SAFETY:
+    // The loader only starts host processes for the libraries of its search paths
+    // or for the private copy of a library it checked against its trust policy.
+    let library = unsafe { Library::new(library_path)? };
+    let stream = UnixStream::connect(socket)?;
+    let reader = stream.try_clone()?;
+    // The loader bounds the time it waits for the host, the host waits for the loader as long as it takes.
+    let connection = Connection::new(stream, None, serve);
+    HOST.store(Some(Arc::new(Host {
+        connection: Arc::clone(&connection),
+        event_handlers: LockedMap::default(),
+        library,
+        request_handlers: LockedMap::default(),
+    })));
+    connection.listen(reader);
+    HOST.store(None);
+    Ok(())
+}
+
+///
+/// Host processes talk to the loader over Unix sockets only.
+///
+#[cfg(not(unix))]
+pub fn run(_: &Path, _: &Path) -> Result<(), HostError> {
+    Err(io::Error::from(io::ErrorKind::Unsupported).into())
+}
+
+fn host() -> Result<Arc<Host>, ServiceError> {
+    HOST.load_full().error(ServiceError::CoreInternalError)
+}
+
+///
+/// Serves a call of the loader with the plugin.
+///
+#[expect(clippy::single_call_fn, reason = "function is the callback of the connection")]
+fn serve(_: &Arc<Connection>, method_name: &str, params: Value) -> Result<Value, ServiceError> {
+    let current = host()?;
+    match method_name {
+        method::HANDLE => {
+            let call = rpc::decode::<HandleParams>(params)?;
+            let handler = current.event_handlers.load().get(&call.handler_id).copied().error(ServiceError::NotFound)?;
+            EventHandler::new_unsafe(handler, call.handler_id).handle(HostContextSupplier, call.args.to_string())?;
+            Ok(Value::Null)
+        }
+        method::MAIN => start(&current, &rpc::decode(params)?),
+        method::REQUEST => {
+            let call = rpc::decode::<RequestParams>(params)?;
+            let handler = current.request_handlers.load().get(&call.handler_id).copied().error(ServiceError::NotFound)?;
+            let response = handler.to_safe_fp()(HostContextSupplier, call.plugin_name, call.args.to_string())?;
+            serde_json::from_str(&response).error(ServiceError::PluginInternalError)
+        }
+        method::UNLOAD => {
+            // SAFETY:
+            // plugin_unload is an optional part of the c-api without arguments or return value.
+            // Finding the symbol implies that the library implements it according to the c-api.
+            if let Ok(unload) = unsafe { current.library.get::<Symbol<unsafe extern "C" fn()>>(b"plugin_unload") } {
+                // SAFETY:
+                // Calling plugin_unload is defined by the c-api. The loader doesn't call the plugin anymore.
+                unsafe { unload(); }
+            }
+            Ok(Value::Null)
+        }
+        _ => Err(ServiceError::NotFound),
+    }
+}
+
+///
+/// Calls `plugin_main` of the library and describes the plugin to the loader.
+/// The init handler of the plugin is registered like any other handler of it.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn start(current: &Host, call: &MainParams) -> Result<Value, ServiceError> {
+    let main =
+    // SAFETY:
+    // Finding the symbol plugin_main implies that the library is implementing
+    // a plugin and therefore should provide the correct api according to the c-api.
+    unsafe { current.library.get::<Symbol<unsafe extern "C" fn(CUuid) -> CPluginInfo>>(b"plugin_main") }
+        .error(ServiceError::NotFound)?;
+    // SAFETY:
+    // Calling plugin_main with the given arguments and return value is defined
+    // by the c-api and therefore considered to be expected to work.
+    let plugin_info = unsafe { main(call.plugin_id.into()) }.to_rust()?;
+    let init_handler = Uuid::new_v4();
+    current.event_handlers.rcu(|handlers| handlers.update(init_handler, plugin_info.handler()));
+    // SAFETY:
+    // plugin_metadata is an optional part of the c-api without arguments returning a PluginMetadata.
+    // Finding the symbol implies that the library implements it according to the c-api.
+    let details = match unsafe { current.library.get::<Symbol<unsafe extern "C" fn() -> CPluginMetadata>>(b"plugin_metadata") } {
+        Ok(metadata_fn) if plugin_info.api_version().offers("plugin_metadata") => {
+            // SAFETY:
+            // Calling plugin_metadata is defined by the c-api.
+            PluginDetails::from_native(&unsafe { metadata_fn() }.to_rust())?
+        }
+        Ok(_) | Err(_) => PluginDetails::default(),
+    };
+    let api_version = plugin_info.api_version();
+    let result = MainResult {
+        api_version: api_version.to_string().into(),
+        dependencies: plugin_info
+            .dependencies()
+            .error(ServiceError::InvalidString)?
+            .into_iter()
+            .map(Box::from)
+            .collect(),
+        init_handler,
+        details,
+        name: plugin_info.name().error(ServiceError::InvalidString)?.into(),
+        version: plugin_info.version().error(ServiceError::InvalidString)?.into(),
+    };
+    serde_json::to_value(result).error(ServiceError::PluginInternalError)
+}
+
+///
+/// Parses a schema or arguments of the plugin to embed them into a call to the loader.
+///
+fn to_json(json: &str) -> Result<Value, ServiceError> {
+    serde_json::from_str(json).error(ServiceError::InvalidJson)
+}
+
+#[trait_fn(ContextSupplier for HostContextSupplier)]
+fn supply() -> ApplicationContext {
+    ApplicationContext::new::<
+        HostHandlerRegister,
+        HostHandlerUnregister,
+        HostEventRegister,
+        HostEventUnregister,
+        HostEventTrigger,
+        HostEndpointRegister,
+        HostEndpointUnregister,
+        HostEndpointRequest,
+        HostEventTriggerBlocking,
+    >()
+}
+
+#[trait_fn(EventHandlerRegisterService for HostHandlerRegister)]
+fn register<T: AsRef<str>>(
+    handler: EventHandlerFuncUnsafeFP,
+    plugin_id: Uuid,
+    event_name: T,
+    priority: i32,
+) -> Result<EventHandler, ServiceError> {
+    let current = host()?;
+    let handler_id = Uuid::new_v4();
+    current.event_handlers.rcu(|handlers| handlers.update(handler_id, handler));
+    let params = RegisterHandlerParams { event_name: event_name.as_ref().into(), handler_id, plugin_id, priority };
+    if let Err(error) = rpc::encode(params).and_then(|json| current.connection.call(method::REGISTER_HANDLER, json)) {
+        current.event_handlers.rcu(|handlers| handlers.without(&handler_id));
+        return Err(error);
+    }
+    Ok(EventHandler::new_unsafe(handler, handler_id))
+}
+
+#[trait_fn(EventHandlerUnregisterService for HostHandlerUnregister)]
+fn unregister<S: AsRef<str>>(
+    handler_id: Uuid,
+    plugin_id: Uuid,
+    event_name: S,
+) -> Result<(), ServiceError> {
+    let current = host()?;
+    let params = UnregisterHandlerParams { event_name: event_name.as_ref().into(), handler_id, plugin_id };
+    current.connection.call(method::UNREGISTER_HANDLER, rpc::encode(params)?)?;
+    current.event_handlers.rcu(|handlers| handlers.without(&handler_id));
+    Ok(())
+}
+
+#[trait_fn(EventRegisterService for HostEventRegister)]
+fn register<S: AsRef<str>, T: AsRef<str>>(
+    event_schema: S,
+    plugin_id: Uuid,
+    event_name: T,
+    dispatch: EventDispatch,
+    max_concurrency: Option<NonZeroUsize>,
+) -> Result<(), ServiceError> {
+    let params = RegisterEventParams {
+        argument_schema: to_json(event_schema.as_ref())?,
+        dispatch: dispatch.into(),
+        event_name: event_name.as_ref().into(),
+        max_concurrency,
+        plugin_id,
+    };
+    host()?.connection.call(method::REGISTER_EVENT, rpc::encode(params)?).map(drop)
+}
+
+#[trait_fn(EventUnregisterService for HostEventUnregister)]
+fn unregister<S: AsRef<str>>(plugin_id: Uuid, event_name: S) -> Result<(), ServiceError> {
+    let params = UnregisterEventParams { event_name: event_name.as_ref().into(), plugin_id };
+    host()?.connection.call(method::UNREGISTER_EVENT, rpc::encode(params)?).map(drop)
+}
+
+#[trait_fn(EventTriggerService for HostEventTrigger)]
+fn trigger<S: AsRef<str>, T: AsRef<str>>(
+    plugin_id: Uuid,
+    event_name: S,
+    args: T,
+) -> Result<(), ServiceError> {
+    let params = TriggerEventParams { args: to_json(args.as_ref())?, event_name: event_name.as_ref().into(), plugin_id };
+    host()?.connection.call(method::TRIGGER_EVENT, rpc::encode(params)?).map(drop)
+}
+
+#[trait_fn(EventTriggerBlockingService for HostEventTriggerBlocking)]
+fn trigger_blocking<S: AsRef<str>, T: AsRef<str>>(
+    plugin_id: Uuid,
+    event_name: S,
+    args: T,
+    timeout: Option<Duration>,
+) -> Result<String, ServiceError> {
+    let params = TriggerEventBlockingParams {
+        args: to_json(args.as_ref())?,
+        event_name: event_name.as_ref().into(),
+        plugin_id,
+        timeout_ms: timeout.map(|duration| u64::try_from(duration.as_millis()).unwrap_or(u64::MAX)),
+    };
+    Ok(host()?.connection.call(method::TRIGGER_EVENT_BLOCKING, rpc::encode(params)?)?.to_string())
+}
+
+#[trait_fn(EndpointRegisterService for HostEndpointRegister)]
+fn register<S: AsRef<str>, T: AsRef<str>, Q: AsRef<str>>(
+    args_schema: S,
+    response_schema: T,
+    plugin_id: Uuid,
+    endpoint_name: Q,
+    handler: RequestHandlerFuncUnsafeFP,
+) -> Result<(), ServiceError> {
+    let current = host()?;
+    let handler_id = Uuid::new_v4();
+    current.request_handlers.rcu(|handlers| handlers.update(handler_id, handler));
+    let params = RegisterEndpointParams {
+        argument_schema: to_json(args_schema.as_ref())?,
+        endpoint_name: endpoint_name.as_ref().into(),
+        handler_id,
+        plugin_id,
+        response_schema: to_json(response_schema.as_ref())?,
+    };
+    if let Err(error) = rpc::encode(params).and_then(|json| current.connection.call(method::REGISTER_ENDPOINT, json)) {
+        current.request_handlers.rcu(|handlers| handlers.without(&handler_id));
+        return Err(error);
+    }
+    Ok(())
+}
+
+#[trait_fn(EndpointUnregisterService for HostEndpointUnregister)]
+fn unregister<S: AsRef<str>>(plugin_id: Uuid, endpoint_name: S) -> Result<(), ServiceError> {
+    let params = UnregisterEndpointParams { endpoint_name: endpoint_name.as_ref().into(), plugin_id };
+    host()?.connection.call(method::UNREGISTER_ENDPOINT, rpc::encode(params)?).map(drop)
+}
+
+#[trait_fn(EndpointRequestService for HostEndpointRequest)]
+fn request<'args, S: AsRef<str>, T: Into<Cow<'args, str>>>(
+    endpoint_name: S,
+    plugin_id: Uuid,
+    args: T,
+) -> Result<String, ServiceError> {
+    let params = RequestEndpointParams { args: to_json(&args.into())?, endpoint_name: endpoint_name.as_ref().into(), plugin_id };
+    Ok(host()?.connection.call(method::REQUEST_ENDPOINT, rpc::encode(params)?)?.to_string())
+}
@@ -5,7 +5,9 @@ use thiserror::Error;
 
 use crate::{
     config::{Config, ConfigError},
-    governor::{GovernorLifetime, get_gov},
+    governor::{GovernorError, GovernorLifetime, get_gov},
+    host::{self, HostError},
+    loader::{Loader, LoaderError},
     runtime::{PowerState, Runtime, RuntimeError},
 };
 
@@ -17,7 +19,16 @@ pub struct Launcher {
 impl Launcher {
     pub fn launch(&self) -> Result<(), LaunchError> {
         let gov_lifetime = GovernorLifetime::new();
+        if let Some((library, socket)) = get_gov()?.cli().host() {
+            host::run(library, socket)?;
+            return Ok(());
+        }
         Config::set_config_dir(&self.config_path)?;
+        if get_gov()?.cli().check_plugins() {
+            Config::init()?;
+            Loader::check_manifests()?;
+            return Ok(());
+        }
         Runtime::start()?;
         ctrlc::set_handler(ctrlc_handler)?;
         loop {
@@ -44,6 +55,9 @@ impl Launcher {
 pub enum LaunchError {
     Config(#[from] ConfigError),
     Ctrlc(#[from] ctrlc::Error),
+    Governor(#[from] GovernorError),
+    Host(#[from] HostError),
+    Loader(#[from] LoaderError),
     Runtime(#[from] RuntimeError),
 }
 
@@ -0,0 +1,418 @@
+use alloc::collections::{BTreeMap, BTreeSet};
+use core::{fmt, slice, str::FromStr};
+
+use derive_more::Display;
+use im::HashMap;
+use semver::{Version, VersionReq};
+use thiserror::Error;
+use topo_sort::TopoSort;
+
+const AFTER_PREFIX: &str = "after:";
+const BEFORE_PREFIX: &str = "before:";
+const OPTIONAL_PREFIX: &str = "?";
+const REQUIREMENT_START: [char; 7] = ['@', '<', '>', '=', '^', '~', '*'];
+
+///
+/// A dependency of a plugin on another plugin.
+/// The dependency is declared as the plugins name optionally followed by a semver version requirement
+/// like `"storage"`, `"storage@^1.2"` or `"auth>=0.3,<0.5"`.
+/// The name may be prefixed to declare an optional dependency (`"?metrics@^1"`)
+/// or a pure ordering hint (`"after:logger"`, `"before:ui"`).
+///
+#[derive(Clone, Debug)]
+pub struct Dependency {
+    kind: DependencyKind,
+    name: Box<str>,
+    requirement: VersionReq,
+    source: Box<str>,
+}
+
+impl Dependency {
+    pub const fn kind(&self) -> DependencyKind {
+        self.kind
+    }
+
+    ///
+    /// Checks if the given plugin version satisfies the version requirement of this dependency.
+    /// Versions that aren't valid semver versions only satisfy dependencies without a requirement.
+    ///
+    pub fn matches(&self, version: &str) -> bool {
+        if self.requirement == VersionReq::STAR {
+            return true;
+        }
+        Version::parse(version).is_ok_and(|parsed| self.requirement.matches(&parsed))
+    }
+
+    #[expect(clippy::single_call_fn, reason = "getter is mostly called as a method")]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl AsRef<str> for Dependency {
+    fn as_ref(&self) -> &str {
+        &self.source
+    }
+}
+
+impl fmt::Display for Dependency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl FromStr for Dependency {
+    type Err = DependencyError;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        let trimmed = source.trim();
+        let (kind, unprefixed) = DependencyKind::split_prefix(trimmed);
+        let (name, requirement_source) = unprefixed
+            .find(|character: char| REQUIREMENT_START.contains(&character) || character.is_whitespace())
+            .map_or((unprefixed, ""), |index| unprefixed.split_at(index));
+        if name.is_empty() {
+            return Err(DependencyError::MissingName);
+        }
+        let stripped_requirement = requirement_source.trim_start().trim_start_matches('@');
+        let requirement = if stripped_requirement.is_empty() {
+            VersionReq::STAR
+        } else {
+            VersionReq::parse(stripped_requirement)?
+        };
+        Ok(Self {
+            kind,
+            name: name.into(),
+            requirement,
+            source: trimmed.into(),
+        })
+    }
+}
+
+///
+/// How a dependency affects the plugin declaring it.
+///
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DependencyKind {
+    /// Only orders the init of the declaring plugin after the named plugin if it is loaded.
+    After,
+    /// Only orders the init of the declaring plugin before the named plugin if it is loaded.
+    Before,
+    /// Orders the init after the named plugin if it is loaded and is ignored otherwise.
+    Optional,
+    /// The named plugin has to be loaded and is initialized before the declaring plugin.
+    Required,
+}
+
+impl DependencyKind {
+    #[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+    fn split_prefix(source: &str) -> (Self, &str) {
+        [(OPTIONAL_PREFIX, Self::Optional), (AFTER_PREFIX, Self::After), (BEFORE_PREFIX, Self::Before)]
+            .into_iter()
+            .find_map(|(prefix, kind)| Some((kind, source.strip_prefix(prefix)?.trim_start())))
+            .unwrap_or((Self::Required, source))
+    }
+}
+
+///
+/// A problem found while validating the dependencies between the loaded plugins.
+///
+#[derive(Clone, Debug, Display)]
+pub enum DependencyIssue {
+    #[display("plugin \"{}\" is part of the dependency cycle: {}", path.first().map_or("", AsRef::as_ref), path.join(" -> "))]
+    Cycle { path: Box<[Box<str>]> },
+    #[display("plugin \"{plugin}\" depends on \"{dependency}\" which is not loaded")]
+    Missing { dependency: Box<str>, plugin: Box<str> },
+    #[display("plugin \"{plugin}\" requires \"{constraint}\" but found version: {found}")]
+    Unsatisfied {
+        constraint: Box<str>,
+        found: Box<str>,
+        plugin: Box<str>,
+    },
+}
+
+impl DependencyIssue {
+    ///
+    /// The names of the plugins that can't be initialized because of this issue.
+    ///
+    #[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+    pub fn affected_plugins(&self) -> &[Box<str>] {
+        match self {
+            Self::Cycle { path } => path.get(1..).unwrap_or_default(),
+            Self::Missing { plugin, .. } | Self::Unsatisfied { plugin, .. } => slice::from_ref(plugin),
+        }
+    }
+}
+
+///
+/// A list of `DependencyIssue`s displayed one per line.
+///
+#[derive(Clone, Debug)]
+pub struct DependencyIssues(pub Box<[DependencyIssue]>);
+
+impl fmt::Display for DependencyIssues {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for issue in &self.0 {
+            writeln!(f, "{issue}")?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Display, Error)]
+pub enum DependencyError {
+    InvalidRequirement(#[from] semver::Error),
+    MissingName,
+}
+
+///
+/// Validates the dependencies of the given plugins given as pairs of name, version and dependencies.
+/// Reports every missing dependency, every unsatisfied version requirement and every dependency cycle.
+/// An optional dependency whose version doesn't satisfy its requirement counts as absent and isn't an issue.
+///
+pub fn validate<'plugin, I>(plugins: I) -> Vec<DependencyIssue>
+where
+    I: IntoIterator<Item = (&'plugin str, &'plugin str, &'plugin [Dependency])>,
+{
+    let by_name = plugins
+        .into_iter()
+        .map(|(name, version, dependencies)| (name, (version, dependencies)))
+        .collect::<BTreeMap<_, _>>();
+    let mut issues = Vec::new();
+    for (name, &(_, dependencies)) in &by_name {
+        for dependency in dependencies {
+            match (dependency.kind(), by_name.get(dependency.name())) {
+                (DependencyKind::Required, None) => {
+                    issues.push(DependencyIssue::Missing { dependency: dependency.name().into(), plugin: (*name).into() });
+                }
+                (DependencyKind::Required, Some(&(version, _))) if !dependency.matches(version) => {
+                    issues.push(DependencyIssue::Unsatisfied {
+                        constraint: dependency.source().into(),
+                        found: version.into(),
+                        plugin: (*name).into(),
+                    });
+                }
+                _ => {}
+            }
+        }
+    }
+    let graph = order_graph(by_name.iter().map(|(name, &(_, dependencies))| (*name, dependencies)));
+    issues.extend(find_cycles(&graph).into_iter().map(|path| DependencyIssue::Cycle { path }));
+    issues
+}
+
+///
+/// Numbers the plugins given as pairs of name and dependencies in their init order.
+/// The dependencies decide the order first and the names of the plugins where they don't.
+/// Returns an empty order if the dependencies contain a cycle.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+pub fn init_order<'plugin, I>(plugins: I) -> HashMap<Box<str>, usize>
+where
+    I: IntoIterator<Item = (&'plugin str, &'plugin [Dependency])>,
+{
+    let mut sorter = TopoSort::new();
+    for (name, earlier) in order_graph(plugins) {
+        sorter.insert(name, earlier);
+    }
+    sorter
+        .try_vec_nodes()
+        .map(|names| names.into_iter().enumerate().map(|(position, name)| ((*name).into(), position)).collect())
+        .unwrap_or_default()
+}
+
+///
+/// Builds the init order of the given plugins given as pairs of name and dependencies.
+/// Every plugin is mapped to the loaded plugins that have to be initialized before it.
+/// Dependencies on plugins that aren't part of the given plugins are ignored.
+///
+fn order_graph<'plugin, I>(plugins: I) -> BTreeMap<&'plugin str, Vec<&'plugin str>>
+where
+    I: IntoIterator<Item = (&'plugin str, &'plugin [Dependency])>,
+{
+    let by_name = plugins.into_iter().collect::<BTreeMap<_, _>>();
+    let mut graph = by_name.keys().map(|name| (*name, Vec::new())).collect::<BTreeMap<_, _>>();
+    for (name, dependencies) in &by_name {
+        for dependency in *dependencies {
+            let Some((target, _)) = by_name.get_key_value(dependency.name()) else {
+                continue;
+            };
+            let (later, earlier) = match dependency.kind() {
+                DependencyKind::Before => (*target, *name),
+                DependencyKind::After | DependencyKind::Optional | DependencyKind::Required => (*name, *target),
+            };
+            if let Some(edges) = graph.get_mut(later) {
+                edges.push(earlier);
+            }
+        }
+    }
+    graph
+}
+
+///
+/// Finds all cycles in the dependency graph. Every cycle is reported once as the path
+/// starting and ending with the same plugin.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn find_cycles(graph: &BTreeMap<&str, Vec<&str>>) -> Vec<Box<[Box<str>]>> {
+    let mut cycles = Vec::new();
+    let mut reported = BTreeSet::new();
+    let mut finished = BTreeSet::new();
+    for node in graph.keys() {
+        visit(node, graph, &mut Vec::new(), &mut finished, &mut |cycle| {
+            let members = cycle.iter().copied().collect::<BTreeSet<_>>();
+            if reported.insert(members) {
+                cycles.push(cycle.iter().copied().chain(cycle.first().copied()).map(Box::from).collect());
+            }
+        });
+    }
+    cycles
+}
+
+fn visit<'graph, F: FnMut(&[&'graph str])>(
+    node: &'graph str,
+    graph: &BTreeMap<&'graph str, Vec<&'graph str>>,
+    stack: &mut Vec<&'graph str>,
+    finished: &mut BTreeSet<&'graph str>,
+    on_cycle: &mut F,
+) {
+    if finished.contains(node) {
+        return;
+    }
+    if let Some(start) = stack.iter().position(|entry| *entry == node) {
+        on_cycle(stack.get(start..).unwrap_or_default());
+        return;
+    }
+    stack.push(node);
+    for next in graph.get(node).into_iter().flatten() {
+        visit(next, graph, stack, finished, on_cycle);
+    }
+    stack.pop();
+    finished.insert(node);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Dependency, DependencyError, DependencyKind, validate};
+
+    ///
+    /// Validates plugins given as name, version and dependency sources and displays the issues found.
+    ///
+    fn issues(plugins: &[(&str, &str, &[&str])]) -> Option<Vec<String>> {
+        let parsed = plugins
+            .iter()
+            .map(|&(name, version, sources)| {
+                let dependencies = sources.iter().map(|source| source.parse().ok()).collect::<Option<Vec<Dependency>>>()?;
+                Some((name, version, dependencies))
+            })
+            .collect::<Option<Vec<_>>>()?;
+        Some(
+            validate(parsed.iter().map(|(name, version, dependencies)| (*name, *version, dependencies.as_slice())))
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        )
+    }
+
+    fn matches(source: &str, version: &str) -> Option<bool> {
+        Some(source.parse::<Dependency>().ok()?.matches(version))
+    }
+
+    #[test]
+    fn requirements_are_parsed_with_and_without_separator() {
+        assert_eq!(matches("storage@^1.2", "1.4.0"), Some(true), "a caret requirement accepts later minor versions");
+        assert_eq!(matches("storage@^1.2", "2.0.0"), Some(false), "a caret requirement rejects the next major version");
+        assert_eq!(matches("auth>=0.3,<0.5", "0.4.1"), Some(true), "a range without @ is accepted");
+        assert_eq!(matches("auth>=0.3,<0.5", "0.5.0"), Some(false), "the upper bound of a range is exclusive");
+        assert_eq!(matches(" auth >=0.3 ", "0.3.0"), Some(true), "whitespace around the name and requirement is ignored");
+    }
+
+    #[test]
+    fn invalid_dependencies_are_rejected() {
+        assert!(matches!("@^1".parse::<Dependency>(), Err(DependencyError::MissingName)), "a requirement needs a name");
+        assert!(
+            matches!("storage@one".parse::<Dependency>(), Err(DependencyError::InvalidRequirement(_))),
+            "the requirement has to be a semver requirement"
+        );
+    }
+
+    #[test]
+    fn versions_without_semver_only_satisfy_plain_dependencies() {
+        assert_eq!(matches("storage", "nightly"), Some(true), "a dependency without requirement accepts any version");
+        assert_eq!(matches("storage@^1", "nightly"), Some(false), "a requirement rejects versions that aren't semver");
+    }
+
+    #[test]
+    fn satisfied_dependencies_have_no_issues() {
+        let plugins: [(&str, &str, &[&str]); 3] = [("app", "1.0.0", &["storage@^1", "auth"]), ("auth", "0.1.0", &[]), ("storage", "1.2.0", &[])];
+        assert_eq!(issues(&plugins), Some(vec![]), "every dependency is loaded in a matching version");
+    }
+
+    #[test]
+    fn missing_and_unsatisfied_dependencies_are_reported() {
+        let plugins: [(&str, &str, &[&str]); 2] = [("app", "1.0.0", &["storage@^2", "auth"]), ("storage", "1.2.0", &[])];
+        assert_eq!(
+            issues(&plugins),
+            Some(vec![
+                "plugin \"app\" requires \"storage@^2\" but found version: 1.2.0".to_owned(),
+                "plugin \"app\" depends on \"auth\" which is not loaded".to_owned(),
+            ]),
+            "every dependency of a plugin is checked"
+        );
+    }
+
+    #[test]
+    fn cycles_are_reported_once() {
+        let plugins: [(&str, &str, &[&str]); 3] = [("a", "1.0.0", &["b"]), ("b", "1.0.0", &["a"]), ("c", "1.0.0", &["a"])];
+        assert_eq!(
+            issues(&plugins),
+            Some(vec!["plugin \"a\" is part of the dependency cycle: a -> b -> a".to_owned()]),
+            "a cycle is reported once and plugins merely depending on it aren't part of it"
+        );
+    }
+
+    #[test]
+    fn prefixes_declare_the_kind() {
+        let kinds = ["storage", "?metrics@^1", "after:logger", "before: ui"]
+            .map(|source| source.parse::<Dependency>().ok().map(|dependency| dependency.kind()));
+        assert_eq!(
+            kinds,
+            [Some(DependencyKind::Required), Some(DependencyKind::Optional), Some(DependencyKind::After), Some(DependencyKind::Before)],
+            "the prefix decides the kind of a dependency"
+        );
+        assert_eq!(matches("?metrics@^1", "1.3.0"), Some(true), "an optional dependency keeps its requirement");
+    }
+
+    #[test]
+    fn optional_dependencies_and_hints_may_be_missing() {
+        let plugins: [(&str, &str, &[&str]); 2] = [("app", "1.0.0", &["?metrics", "after:logger", "before:ui", "?storage@^2"]), ("storage", "1.2.0", &[])];
+        assert_eq!(
+            issues(&plugins),
+            Some(vec![]),
+            "neither optional dependencies nor ordering hints have to be satisfied"
+        );
+    }
+
+    #[test]
+    fn optional_dependencies_with_another_version_are_inactive() {
+        let plugins: [(&str, &str, &[&str]); 3] = [("app", "1.0.0", &["?storage@^2"]), ("storage", "1.2.0", &[]), ("ui", "1.0.0", &["storage@^2"])];
+        assert_eq!(
+            issues(&plugins),
+            Some(vec!["plugin \"ui\" requires \"storage@^2\" but found version: 1.2.0".to_owned()]),
+            "only a required dependency has to satisfy its requirement"
+        );
+    }
+
+    #[test]
+    fn hints_take_part_in_cycles() {
+        let plugins: [(&str, &str, &[&str]); 2] = [("a", "1.0.0", &["before:b"]), ("b", "1.0.0", &["before:a"])];
+        assert_eq!(
+            issues(&plugins),
+            Some(vec!["plugin \"a\" is part of the dependency cycle: a -> b -> a".to_owned()]),
+            "contradicting ordering hints form a cycle"
+        );
+    }
+}
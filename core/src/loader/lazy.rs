@@ -0,0 +1,131 @@
+use alloc::sync::Arc;
+use std::{path::Path, sync::Mutex};
+
+use plugin_loader_api::{ErrorMapper as _, ServiceError};
+use uuid::Uuid;
+
+use crate::{
+    governor::get_gov,
+    loader::{Loader, LoaderError, manifest::Manifest, reload, report::{self, LoadOutcome}},
+    runtime::{
+        Runtime, RuntimeError,
+        cleanup::{self, RemovalReason},
+        endpoint, event, watchdog,
+    },
+};
+
+///
+/// A plugin that is only loaded once one of its endpoints is requested or one of the events it handles is triggered.
+/// Until then placeholders owned by `placeholder_id` stand in for the endpoints and handlers declared in its manifest.
+///
+#[derive(Clone)]
+pub struct DeferredPlugin {
+    activation: Arc<Mutex<Option<Uuid>>>,
+    manifest: Manifest,
+    path: Box<Path>,
+    placeholder_id: Uuid,
+}
+
+///
+/// Loads and initializes the lazy plugin with the given name and returns its id.
+/// The placeholders are only removed once the init handler of the plugin succeeded. If loading or initializing fails,
+/// the plugin is unloaded again and the placeholders stay, so that the next call tries again. The load report follows either way.
+/// The init handler runs on the event pool. Calls arriving while the plugin is activated wait until it settled.
+/// Calls for a plugin that was activated already return the id of the loaded plugin.
+/// The init handler of the plugin itself can't wait for its own activation and gets `ServiceError::NotFound` instead.
+///
+pub fn activate(name: &str) -> Result<Uuid, RuntimeError> {
+    let Some(lazy) = get_gov()?.loader().lazy().load().get(name).cloned() else {
+        let gov = get_gov()?;
+        let plugins = gov.loader().plugins().load();
+        let activated = plugins.values().find(|plugin| plugin.name() == name).ok_or(LoaderError::UnknownPlugin)?;
+        return Ok(activated.id());
+    };
+    if watchdog::is_handling("core:init", name) {
+        return Err(ServiceError::NotFound.into());
+    }
+    watchdog::while_blocked(|| {
+        let mut activation = lazy.activation.lock().error(ServiceError::CoreInternalError)?;
+        if let Some(plugin_id) = *activation {
+            return Ok(plugin_id);
+        }
+        let plugin_id = match load(name, &lazy.path) {
+            Ok(plugin_id) => plugin_id,
+            Err(error) => {
+                cleanup::remove_registrations(lazy.placeholder_id, RemovalReason::Unload)?;
+                insert_placeholders(&lazy.manifest, lazy.placeholder_id)?;
+                report::record(&lazy.path, &LoadOutcome::Failed { error: error.to_string().into() })?;
+                return Err(error);
+            }
+        };
+        *activation = Some(plugin_id);
+        drop(activation);
+        cleanup::remove_registrations(lazy.placeholder_id, RemovalReason::Unload)?;
+        let gov = get_gov()?;
+        gov.loader().lazy().rcu(|plugins| plugins.without(name));
+        let plugins = gov.loader().plugins().load();
+        let (id, loaded_name) = plugins
+            .get(&plugin_id)
+            .map(|loaded| (loaded.stable_id(), loaded.name()))
+            .unwrap_or_default();
+        report::record(&lazy.path, &LoadOutcome::Loaded { id, name: loaded_name.into() })?;
+        Ok(plugin_id)
+    })
+}
+
+///
+/// Registers the placeholders of a lazy plugin instead of loading its library.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+pub fn defer(path: &Path, manifest: &Manifest) -> Result<(), LoaderError> {
+    let placeholder_id = Uuid::new_v4();
+    if let Err(error) = insert_placeholders(manifest, placeholder_id) {
+        cleanup::remove_registrations(placeholder_id, RemovalReason::Failure)?;
+        return Err(error.into());
+    }
+    let lazy = DeferredPlugin {
+        activation: Arc::default(),
+        manifest: manifest.clone(),
+        path: path.into(),
+        placeholder_id,
+    };
+    get_gov()?.loader().lazy().rcu(|plugins| plugins.update(manifest.name().into(), lazy.clone()));
+    Ok(())
+}
+
+///
+/// Registers a placeholder for every endpoint and event handler declared in the manifest.
+///
+fn insert_placeholders(manifest: &Manifest, placeholder_id: Uuid) -> Result<(), ServiceError> {
+    manifest
+        .endpoints()
+        .iter()
+        .try_for_each(|endpoint_name| endpoint::insert_placeholder(manifest.name(), endpoint_name, placeholder_id))?;
+    manifest
+        .events()
+        .iter()
+        .try_for_each(|event_name| event::insert_placeholder(manifest.name(), event_name, placeholder_id))
+}
+
+///
+/// Loads the plugin and waits until its init handler returned, at most until the init deadline of the core settings.
+/// The plugin is unloaded again if the init handler failed.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn load(name: &str, path: &Path) -> Result<Uuid, RuntimeError> {
+    let plugin_id = reload::load_checked(path, None)?;
+    let deadline = Runtime::init_deadline()?;
+    let failure = match event::init_plugin(plugin_id, Runtime::init_arguments()?).and_then(|completion| completion.wait_settled(deadline)) {
+        Ok(outcomes) => outcomes.into_values().find_map(|outcome| match outcome {
+            Some(Ok(())) => None,
+            Some(Err(error)) => Some(error),
+            None => Some(ServiceError::PluginInternalError),
+        }),
+        Err(error) => Some(error),
+    };
+    if let Some(error) = failure {
+        Loader::unload(name, RemovalReason::Failure)?;
+        return Err(error.into());
+    }
+    Ok(plugin_id)
+}
@@ -0,0 +1,166 @@
+use std::{fs, io, path::Path};
+
+use derive_more::Display;
+use plugin_loader_api::ApiVersion;
+use serde::Deserialize;
+use thiserror::Error;
+use toml::de::Error as TomlError;
+
+const MANIFEST_EXTENSIONS: [&str; 2] = ["toml", "json"];
+
+///
+/// The sidecar manifest of a plugin library.
+/// It is read before the library is loaded and allows validating the plugin without executing any foreign code.
+/// The manifest of `libfoo.so` is either `libfoo.toml` or `libfoo.json` in the same directory.
+/// An optional `kind` declares how the plugin is run, for example `kind = "executable"` for a script.
+/// A plugin with `lazy = true` is only loaded once one of the declared `endpoints` is requested
+/// or one of the declared `events` is triggered. The events have to exist at the start, like the events of the core.
+///
+#[derive(Deserialize, Debug, Clone)]
+pub struct Manifest {
+    api_version: Box<str>,
+    #[serde(default)]
+    dependencies: Box<[Box<str>]>,
+    #[serde(default)]
+    endpoints: Box<[Box<str>]>,
+    #[serde(default)]
+    events: Box<[Box<str>]>,
+    #[serde(default)]
+    kind: Option<PluginKind>,
+    #[serde(default)]
+    lazy: bool,
+    name: Box<str>,
+    version: Box<str>,
+}
+
+///
+/// How the code of a plugin is run.
+///
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PluginKind {
+    /// An executable exchanging JSON lines with the loader over its stdin and stdout.
+    Executable,
+    /// A native library implementing the c-api.
+    Library,
+    /// A module compiled to WebAssembly.
+    Wasm,
+}
+
+impl Manifest {
+    pub fn api_version(&self) -> Result<ApiVersion, ManifestError> {
+        parse_api_version(&self.api_version).ok_or(ManifestError::InvalidApiVersion)
+    }
+
+    ///
+    /// Whether the sidecar manifest of the given file declares it as an executable or WebAssembly plugin.
+    /// Other files next to a library, like `libfoo.bak`, share the manifest of `libfoo.so` but aren't plugins.
+    /// A manifest that can't be read counts as a declaration, so that its error is reported when the file is loaded.
+    ///
+    #[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+    pub fn declares_plugin(file: &Path) -> bool {
+        Self::find(file).map_or(true, |manifest| {
+            manifest.is_some_and(|found| matches!(found.kind, Some(PluginKind::Executable | PluginKind::Wasm)))
+        })
+    }
+
+    pub fn dependencies(&self) -> &[Box<str>] {
+        &self.dependencies
+    }
+
+    ///
+    /// The names of the endpoints a lazy plugin registers without the prefix of the plugin.
+    ///
+    pub fn endpoints(&self) -> &[Box<str>] {
+        &self.endpoints
+    }
+
+    ///
+    /// The full names of the events a lazy plugin handles.
+    ///
+    pub fn events(&self) -> &[Box<str>] {
+        &self.events
+    }
+
+    ///
+    /// Looks for the manifest of the given library file.
+    /// Returns `None` if the library has no sidecar manifest.
+    ///
+    pub fn find(library: &Path) -> Result<Option<Self>, ManifestError> {
+        let Some(path) = MANIFEST_EXTENSIONS.iter()
+            .map(|extension| library.with_extension(extension))
+            .find(|path| path.is_file())
+        else {
+            return Ok(None);
+        };
+        let content = fs::read_to_string(&path)?;
+        let manifest = if path.extension().is_some_and(|extension| extension == "json") {
+            serde_json::from_str(&content)?
+        } else {
+            toml::from_str(&content)?
+        };
+        Ok(Some(manifest))
+    }
+
+    pub const fn is_lazy(&self) -> bool {
+        self.lazy
+    }
+
+    #[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+    pub fn is_manifest(path: &Path) -> bool {
+        path.extension()
+            .is_some_and(|extension| MANIFEST_EXTENSIONS.iter().any(|manifest_extension| extension == *manifest_extension))
+    }
+
+    #[expect(clippy::single_call_fn, reason = "getter is mostly called as a method")]
+    pub const fn kind(&self) -> Option<PluginKind> {
+        self.kind
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+}
+
+#[derive(Debug, Display, Error)]
+pub enum ManifestError {
+    IO(#[from] io::Error),
+    InvalidApiVersion,
+    Json(#[from] serde_json::Error),
+    Toml(#[from] TomlError),
+}
+
+///
+/// Parses an api version of the form "major.feature" or "major.feature.patch".
+///
+pub fn parse_api_version(api_version: &str) -> Option<ApiVersion> {
+    let mut parts = api_version.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let feature = parts.next()?.parse().ok()?;
+    let patch = parts.next().map_or(Some(0), |patch| patch.parse().ok())?;
+    if parts.next().is_some() {
+        return None;
+    }
+    Some(ApiVersion { major, feature, patch })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_api_version;
+
+    #[test]
+    fn api_versions_are_parsed_with_optional_patch() {
+        let parsed = ["0.2", "1.3.4"].map(|source| parse_api_version(source).map(|version| version.to_string()));
+        assert_eq!(parsed, [Some("0.2.0".to_owned()), Some("1.3.4".to_owned())], "a missing patch version is 0");
+    }
+
+    #[test]
+    fn malformed_api_versions_are_rejected() {
+        let parsed = ["", "1", "1.x", "1.2.3.4", "1.256", "-1.2"].map(|source| parse_api_version(source).is_some());
+        assert_eq!(parsed, [false; 6], "only major.feature or major.feature.patch within the ranges of the api version are accepted");
+    }
+}
@@ -0,0 +1,1000 @@
+mod dependency;
+pub mod lazy;
+mod manifest;
+mod process;
+pub mod reload;
+pub mod report;
+mod trust;
+mod wasm;
+
+use core::{str::FromStr as _, sync::atomic::{AtomicBool, AtomicUsize, Ordering}};
+use std::{env, fs, path::{Path, PathBuf}};
+use alloc::sync::Arc;
+use derive_more::Display;
+use atomic_once_cell::AtomicOnceCell;
+use im::Vector;
+use notify::RecommendedWatcher;
+use plugin_loader_api::{API_VERSION, ApiVersion, EventHandler, PluginInfo, STATIC_PLUGINS, ServiceError, StaticPlugin, cbindings::{CPluginInfo, CPluginMetadata, CUuid}, misc::ApiMiscError};
+use libloading::{Library, Symbol};
+
+use std::io;
+use thiserror::Error;
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    config::{ConfigError, PLUGIN_PATH_VAR, settings::DependencyPolicy}, governor::{GovernorError, get_gov}, loader::{dependency::{DependencyError, DependencyIssue, DependencyIssues}, lazy::DeferredPlugin, manifest::{Manifest, ManifestError, PluginKind}, process::PluginProcess, report::{LoadEntry, LoadOutcome}, trust::PrivateCopy, wasm::WasmModule}, rpc::{self, MainParams, MainResult, Peer, PluginDetails}, runtime::{cleanup::{self, RemovalReason}, event::{Handler, StoredEventHandler}}, util::{ArcMapExt as _, LockedMap, LockedVec, TrueOrErr as _}
+};
+
+pub use dependency::{Dependency, DependencyKind};
+
+///
+/// The extensions of files that are plugins without a manifest.
+///
+const PLUGIN_EXTENSIONS: [&str; 3] = [env::consts::DLL_EXTENSION, "exe", "wasm"];
+
+///
+/// The oldest api version the loader still accepts plugins of. Plugins declare the api version they need at least
+/// and are accepted if it lies between this version and `API_VERSION`. Optional functions newer than the declared version aren't used.
+/// 0.2 added the blocking trigger to `ApplicationContext`, which plugins get by value. 0.1 plugins expect the smaller struct and aren't accepted.
+///
+const MIN_API_VERSION: ApiVersion = ApiVersion { major: 0, feature: 2, patch: 0 };
+
+///
+/// The namespace the stable ids of plugins are derived from together with their names.
+///
+const PLUGIN_ID_NAMESPACE: Uuid = Uuid::from_u128(0x2a74_1be5_b1d0_45e0_b490_e55c_c25e_9227);
+
+///
+/// The directory the paths of plugins linked into the loader binary are placed in. It is never looked up in the file system.
+///
+const STATIC_PLUGIN_DIR: &str = "<static>";
+
+pub type Plugins = LockedMap<Uuid, Plugin>;
+
+///
+/// Where the code of a plugin runs.
+///
+#[derive(Clone)]
+enum Backend {
+    /// The library is loaded into the loader itself.
+    Library(Arc<Library>),
+    /// The library is loaded by a host process of its own or the plugin is an executable.
+    Process(Arc<PluginProcess>),
+    /// The plugin is linked into the loader binary.
+    Static,
+    /// The module runs in the embedded WebAssembly runtime of the loader.
+    Wasm(Arc<WasmModule>),
+}
+
+#[derive(Default)]
+pub struct Loader {
+    /// The position of every loaded plugin in the init order. It is updated whenever a plugin is loaded or removed.
+    init_order: LockedMap<Box<str>, usize>,
+    lazy: LockedMap<Box<str>, DeferredPlugin>,
+    plugins: LockedMap<Uuid, Plugin>,
+    report: LockedVec<LoadEntry>,
+    respawns: LockedMap<Box<str>, usize>,
+    watcher: AtomicOnceCell<RecommendedWatcher>,
+}
+
+///
+/// What a plugin returned from its main function.
+///
+struct MainInfo {
+    api_version: ApiVersion,
+    dependencies: Box<[Box<str>]>,
+    details: PluginDetails,
+    init_handler: Handler,
+    name: Box<str>,
+    version: Box<str>,
+}
+
+///
+/// A loaded plugin. Its library or host process stays alive as long as any clone of it is held.
+/// A clone is held while calling into the plugin, so that an unloaded plugin is only closed after the last call returned.
+///
+#[derive(Clone)]
+pub struct Plugin {
+    api_version: ApiVersion,
+    backend: Backend,
+    dependencies: Box<[Dependency]>,
+    details: Arc<PluginDetails>,
+    failed: Arc<AtomicBool>,
+    faults: Arc<AtomicUsize>,
+    handler_errors: Arc<AtomicUsize>,
+    /// The secret passed to the plugin on load that authorises its calls. It changes every time the plugin is loaded.
+    id: Uuid,
+    name: Arc<str>,
+    path: Box<Path>,
+    /// The id of the plugin derived from its name that stays the same across restarts.
+    stable_id: Uuid,
+    version: Box<str>,
+}
+
+impl MainInfo {
+    ///
+    /// Converts what the main function of a plugin in the loader process returned.
+    /// The metadata function is only called if the plugin declares an api version offering it.
+    ///
+    unsafe fn native(
+        plugin_info: &PluginInfo,
+        metadata_fn: Option<unsafe extern "C" fn() -> CPluginMetadata>,
+    ) -> Result<Self, LoaderError> {
+        let details = match metadata_fn {
+            Some(metadata) if plugin_info.api_version().offers("plugin_metadata") => {
+                // SAFETY:
+                // Calling plugin_metadata is defined by the c-api.
+                PluginDetails::from_native(&unsafe { metadata() }.to_rust())?
+            }
+            Some(_) | None => PluginDetails::default(),
+        };
+        Ok(Self {
+            api_version: plugin_info.api_version(),
+            dependencies: plugin_info.dependencies()?.into_iter().map(Box::from).collect(),
+            init_handler: Handler::Native(EventHandler::new_unsafe(plugin_info.handler(), Uuid::new_v4())),
+            details,
+            name: plugin_info.name()?.into(),
+            version: plugin_info.version()?.into(),
+        })
+    }
+
+    ///
+    /// Calls the main function of a plugin that isn't a native library in the loader process.
+    ///
+    fn remote(peer: Arc<dyn Peer>, plugin_id: Uuid) -> Result<Self, LoaderError> {
+        let result = rpc::decode::<MainResult>(peer.call(rpc::method::MAIN, rpc::encode(MainParams { plugin_id })?)?)?;
+        Ok(Self {
+            api_version: manifest::parse_api_version(&result.api_version).ok_or(LoaderError::InvalidApiVersion)?,
+            dependencies: result.dependencies,
+            details: result.details,
+            init_handler: Handler::Remote { handler_id: result.init_handler, peer },
+            name: result.name,
+            version: result.version,
+        })
+    }
+}
+
+impl Plugin {
+    ///
+    /// Counts a fault of this plugin and returns the number of faults counted so far.
+    ///
+    pub fn add_fault(&self) -> usize {
+        self.faults.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    ///
+    /// Counts a failed call of an event handler of this plugin and returns the number of failed calls counted so far.
+    ///
+    #[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+    pub fn add_handler_error(&self) -> usize {
+        self.handler_errors.fetch_add(1, Ordering::Relaxed) + 1
+    }
+
+    pub const fn api_version(&self) -> ApiVersion {
+        self.api_version
+    }
+
+    pub const fn dependencies(&self) -> &[Dependency] {
+        &self.dependencies
+    }
+
+    pub fn details(&self) -> &PluginDetails {
+        &self.details
+    }
+
+    pub fn handler_errors(&self) -> usize {
+        self.handler_errors.load(Ordering::Relaxed)
+    }
+
+    pub const fn id(&self) -> Uuid {
+        self.id
+    }
+
+    ///
+    /// A failed plugin stays loaded, but can't register events, endpoints or handlers until it is reloaded.
+    ///
+    #[expect(clippy::single_call_fn, reason = "getter is mostly called as a method")]
+    pub fn is_failed(&self) -> bool {
+        self.failed.load(Ordering::Relaxed)
+    }
+
+    pub fn mark_failed(&self) {
+        self.failed.store(true, Ordering::Relaxed);
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub const fn stable_id(&self) -> Uuid {
+        self.stable_id
+    }
+
+    pub const fn version(&self) -> &str {
+        &self.version
+    }
+}
+
+impl Loader {
+    ///
+    /// Counts a respawn of the host process of the plugin with the given name and returns the number of respawns so far.
+    ///
+    pub fn add_respawn(&self, name: &str) -> usize {
+        self.respawns.rcu(|respawns| respawns.update(name.into(), respawns.get(name).map_or(1, |count| count + 1)));
+        self.respawns.load().get(name).copied().unwrap_or_default()
+    }
+
+    ///
+    /// Reads and validates the manifest of a library against the api version and the names in `loaded_names`.
+    ///
+    fn check_manifest(library: &Path, loaded_names: &[Box<str>]) -> Result<Option<Manifest>, LoaderError> {
+        let Some(manifest) = Manifest::find(library)? else {
+            return Ok(None);
+        };
+        check_name(manifest.name())?;
+        check_api_version(manifest.api_version()?)?;
+        if loaded_names.iter().any(|name| **name == *manifest.name()) {
+            return Err(LoaderError::DuplicateName);
+        }
+        Ok(Some(manifest))
+    }
+
+    ///
+    /// Validates the manifests of all plugins in the search paths without loading any of them.
+    /// Every library is reported on stdout, disabled ones without checking their manifest.
+    ///
+    #[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+    #[expect(clippy::print_stdout, reason = "the check is a command line tool for operators")]
+    pub fn check_manifests() -> Result<(), LoaderError> {
+        let mut names = Vec::new();
+        let mut invalid = 0usize;
+        for plugin in Self::plugin_files()? {
+            match Self::is_enabled(&plugin) {
+                Ok(true) => {}
+                Ok(false) => {
+                    println!("{}: disabled", plugin.display());
+                    continue;
+                }
+                Err(error) => {
+                    println!("{}: {error}", plugin.display());
+                    invalid += 1;
+                    continue;
+                }
+            }
+            match Self::check_manifest(&plugin, &names) {
+                Ok(Some(manifest)) => {
+                    println!("{}: \"{}\" version: {}", plugin.display(), manifest.name(), manifest.version());
+                    names.push(Box::from(manifest.name()));
+                },
+                Ok(None) => println!("{}: no manifest", plugin.display()),
+                Err(error) => {
+                    println!("{}: {error}", plugin.display());
+                    invalid += 1;
+                }
+            }
+        }
+        if invalid > 0 {
+            return Err(LoaderError::InvalidManifests(invalid));
+        }
+        Ok(())
+    }
+
+    ///
+    /// Registers the placeholders of the plugin instead of loading it if its manifest declares it as lazy.
+    /// Returns whether the plugin is lazy.
+    ///
+    #[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+    fn defer_lazy(filename: &Path) -> Result<bool, LoaderError> {
+        let gov = get_gov()?;
+        let loaded_names = gov
+            .loader()
+            .plugins()
+            .load()
+            .values()
+            .map(|plugin| Box::from(plugin.name()))
+            .chain(gov.loader().lazy().load().keys().cloned())
+            .collect::<Vec<_>>();
+        match Self::check_manifest(filename, &loaded_names)? {
+            Some(manifest) if manifest.is_lazy() => {
+                lazy::defer(filename, &manifest)?;
+                Ok(true)
+            }
+            Some(_) | None => Ok(false),
+        }
+    }
+
+    ///
+    /// The position of every loaded plugin in the init order. Plugins without a position follow the others ordered by their names.
+    ///
+    pub const fn init_order(&self) -> &LockedMap<Box<str>, usize> {
+        &self.init_order
+    }
+
+    ///
+    /// Whether the plugin is loaded at all according to the `enabled_plugins`, `disabled_plugins` and `plugin_set` of the core settings.
+    ///
+    fn is_enabled(filename: &Path) -> Result<bool, LoaderError> {
+        let manifest = Manifest::find(filename)?;
+        get_gov()?
+            .config()
+            .core_settings()?
+            .is_enabled(&plugin_names(filename, manifest.as_ref()))
+            .ok_or(LoaderError::UnknownPluginSet)
+    }
+
+    ///
+    /// Whether the library is loaded by a host process of its own according to the `isolated_plugins` of the core settings.
+    ///
+    #[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+    fn is_isolated(filename: &Path, manifest: Option<&Manifest>) -> Result<bool, LoaderError> {
+        Ok(get_gov()?.config().core_settings()?.is_isolated(&plugin_names(filename, manifest)))
+    }
+
+    pub const fn lazy(&self) -> &LockedMap<Box<str>, DeferredPlugin> {
+        &self.lazy
+    }
+
+    ///
+    /// Loads all enabled plugins and records the outcome for every plugin file in the load report.
+    /// A plugin that fails to load is reported and skipped unless `strict_loading` is set in the core settings.
+    ///
+    #[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+    pub fn load_libraries() -> Result<(), LoaderError> {
+        let strict = get_gov()?.config().core_settings()?.strict_loading();
+        let mut entries = Vec::new();
+        for plugin in Self::plugin_files()? {
+            match Self::is_enabled(&plugin) {
+                Ok(true) => {}
+                Ok(false) => {
+                    entries.push(LoadEntry::new(&plugin, LoadOutcome::Skipped { reason: "disabled".into() }));
+                    continue;
+                }
+                Err(error) if strict => return Err(error),
+                Err(error) => {
+                    entries.push(LoadEntry::new(&plugin, LoadOutcome::Failed { error: error.to_string().into() }));
+                    continue;
+                }
+            }
+            match Self::defer_lazy(&plugin) {
+                Ok(false) => {}
+                Ok(true) => {
+                    entries.push(LoadEntry::new(&plugin, LoadOutcome::Skipped { reason: "lazy".into() }));
+                    continue;
+                }
+                Err(error) if strict => return Err(error),
+                Err(error) => {
+                    entries.push(LoadEntry::new(&plugin, LoadOutcome::Failed { error: error.to_string().into() }));
+                    continue;
+                }
+            }
+            // SAFETY:
+            // load_library is inherently unsafe as it calls foreign code.
+            // The only safety we have is that we trust the search paths.
+            let entry = match unsafe { Self::load_library(&plugin, None) } {
+                Ok(plugin_id) => {
+                    let gov = get_gov()?;
+                    let plugins = gov.loader().plugins().load();
+                    let (id, name) = plugins
+                        .get(&plugin_id)
+                        .map(|loaded| (loaded.stable_id(), loaded.name()))
+                        .unwrap_or_default();
+                    LoadEntry::new(&plugin, LoadOutcome::Loaded { id, name: name.into() })
+                }
+                Err(error) if strict => return Err(error),
+                Err(error) => LoadEntry::new(&plugin, LoadOutcome::Failed { error: error.to_string().into() }),
+            };
+            entries.push(entry);
+        }
+        get_gov()?.loader().report.store(Arc::new(entries.into()));
+        Ok(())
+    }
+
+    ///
+    /// Loads the plugin file and adds the plugin next to the loaded ones. A plugin being replaced by the file keeps running
+    /// until it is unloaded, its name doesn't count as taken meanwhile.
+    ///
+    unsafe fn load_library(filename: &Path, replaces: Option<Uuid>) -> Result<Uuid, LoaderError> {
+        let loaded_names = get_gov()?
+                .loader()
+                .plugins()
+                .load()
+                .iter()
+                .filter(|&(id, _)| Some(*id) != replaces)
+                .map(|(_, plugin)| Box::from(plugin.name()))
+                .collect::<Vec<_>>();
+        let manifest = Self::check_manifest(filename, &loaded_names)?;
+        let plugin_id = Uuid::new_v4();
+        // SAFETY:
+        // Same as for load_library itself.
+        let (backend, main_info) = unsafe { Self::open(filename, manifest.as_ref(), plugin_id, replaces.is_some())? };
+        check_api_version(main_info.api_version)?;
+        let dependencies = main_info
+            .dependencies
+            .iter()
+            .map(|dependency| Dependency::from_str(dependency))
+            .collect::<Result<Box<[_]>, _>>()?;
+        let new_plugin = Plugin {
+            api_version: main_info.api_version,
+            backend,
+            failed: Arc::default(),
+            faults: Arc::default(),
+            handler_errors: Arc::default(),
+            details: Arc::new(main_info.details),
+            id: plugin_id,
+            stable_id: Uuid::new_v5(&PLUGIN_ID_NAMESPACE, main_info.name.as_bytes()),
+            name: main_info.name.into(),
+            path: filename.into(),
+            version: main_info.version,
+            dependencies,
+        };
+
+        check_name(&new_plugin.name)?;
+        if let Some(expected) = manifest
+            && (expected.name() != &*new_plugin.name
+                || expected.version() != &*new_plugin.version
+                || !expected.dependencies().iter().map(AsRef::as_ref).eq(new_plugin.dependencies.iter().map(AsRef::as_ref)))
+        {
+            return Err(LoaderError::ManifestMismatch);
+        }
+
+            if get_gov()?
+                .loader()
+                .plugins()
+                .load()
+                .iter()
+                .any(|(id, plugin)| Some(*id) != replaces && plugin.name == new_plugin.name)
+            {
+                return Err(LoaderError::DuplicateName);
+            }
+            let handler = StoredEventHandler::new(main_info.init_handler, plugin_id, 0);
+            get_gov()?.events()
+                .rcu_alter("core:init", |event| {
+                    event
+                        .handlers_mut()
+                        .insert(handler.clone())
+                        .or_error(ServiceError::CoreInternalError)
+                }).map_err(|_error| LoaderError::CoreEventsMissing)?;
+            get_gov()?.loader()
+                .plugins()
+                .rcu(|map| map.update(plugin_id, new_plugin.clone()));
+            get_gov()?.loader().update_init_order();
+
+
+        #[expect(clippy::print_stderr, reason = "debug_assertions")]
+        #[cfg(debug_assertions)]
+        {
+            eprintln!(
+                "Loaded Plugin \"{}\" version: {}",
+                new_plugin.name, new_plugin.version
+            );
+        }
+        Ok(plugin_id)
+    }
+
+    ///
+    /// Opens the plugin file according to its kind and calls its `plugin_main` function.
+    /// The file is checked against the trust policy first. Native code is loaded from a private copy of the checked content
+    /// and WebAssembly from the checked content itself, instead of reading the file again.
+    /// A file replacing a loaded plugin is always loaded from a copy, as opening the path of a loaded library again returns that library.
+    ///
+    #[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+    unsafe fn open(filename: &Path, manifest: Option<&Manifest>, plugin_id: Uuid, replacing: bool) -> Result<(Backend, MainInfo), LoaderError> {
+        let linked = static_plugin_at(filename);
+        let verified = match linked {
+            Some(_) => None,
+            None => match trust::verify(filename, get_gov()?.config().core_settings()?.trust())? {
+                None if replacing => Some(fs::read(filename)?),
+                verified => verified,
+            },
+        };
+        let private_copy = || verified.as_deref().map(|content| PrivateCopy::new(filename, content)).transpose();
+        Ok(match plugin_kind(filename, manifest) {
+            // SAFETY:
+            // Same as for load_library itself.
+            _ if let Some(entry) = linked => unsafe { Self::open_static(entry, plugin_id)? },
+            PluginKind::Executable => {
+                let process = PluginProcess::spawn_executable(filename, private_copy()?, plugin_id)?;
+                let main_info = process.main(plugin_id)?;
+                (Backend::Process(Arc::new(process)), main_info)
+            }
+            PluginKind::Library if Self::is_isolated(filename, manifest)? => {
+                // The host process loads the library before it connects, so the copy isn't needed afterwards.
+                let copy = private_copy()?;
+                let process = PluginProcess::spawn(copy.as_ref().map_or(filename, PrivateCopy::path), plugin_id)?;
+                drop(copy);
+                let main_info = process.main(plugin_id)?;
+                (Backend::Process(Arc::new(process)), main_info)
+            }
+            PluginKind::Library => {
+                let copy = private_copy()?;
+                // SAFETY:
+                // Same as for load_library itself.
+                unsafe { Self::open_library(copy.as_ref().map_or(filename, PrivateCopy::path), plugin_id)? }
+            }
+            PluginKind::Wasm => {
+                let content = match verified {
+                    Some(content) => content,
+                    None => fs::read(filename)?,
+                };
+                let module = WasmModule::load(&content, plugin_id)?;
+                let peer: Arc<dyn Peer> = Arc::<WasmModule>::clone(&module);
+                let main_info = MainInfo::remote(peer, plugin_id)?;
+                (Backend::Wasm(module), main_info)
+            }
+        })
+    }
+
+    ///
+    /// Loads the library into the loader itself and calls its `plugin_main` function.
+    ///
+    #[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+    unsafe fn open_library(filename: &Path, plugin_id: Uuid) -> Result<(Backend, MainInfo), LoaderError> {
+        // SAFETY:
+        // Can't do anything to make loading a library safer.
+        let lib = unsafe { Library::new(filename)? };
+        let main =
+        // SAFETY:
+        // Finding the symbol plugin_main implies that the library is implementing
+        // a plugin and therefore should provide the correct api according to the c-api.
+        unsafe { lib.get::<Symbol<unsafe extern "C" fn(CUuid) -> CPluginInfo>>(b"plugin_main")? };
+        // SAFETY:
+        // Calling plugin_main with the given arguments and return value is defined
+        // by the c-api and therefore considered to be expected to work.
+        let plugin_info = unsafe { main(plugin_id.into()) }.to_rust()?;
+        // SAFETY:
+        // plugin_metadata is an optional part of the c-api without arguments returning a PluginMetadata.
+        // Finding the symbol implies that the library implements it according to the c-api.
+        let metadata_fn = unsafe { lib.get::<Symbol<unsafe extern "C" fn() -> CPluginMetadata>>(b"plugin_metadata") }
+            .ok()
+            .map(|symbol| **symbol);
+        // SAFETY:
+        // The metadata function stays valid as long as the library is loaded.
+        let main_info = unsafe { MainInfo::native(&plugin_info, metadata_fn)? };
+        Ok((Backend::Library(Arc::new(lib)), main_info))
+    }
+
+    ///
+    /// Calls the `plugin_main` function of a plugin linked into the loader binary.
+    ///
+    #[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+    unsafe fn open_static(entry: &StaticPlugin, plugin_id: Uuid) -> Result<(Backend, MainInfo), LoaderError> {
+        // SAFETY:
+        // The static_plugin macro only registers functions implementing plugin_main according to the c-api.
+        let plugin_info = unsafe { entry.main()(plugin_id.into()) }.to_rust()?;
+        // SAFETY:
+        // The metadata function is linked into the loader binary and therefore always valid.
+        let main_info = unsafe { MainInfo::native(&plugin_info, entry.metadata())? };
+        if &*main_info.name != entry.name() {
+            return Err(LoaderError::StaticNameMismatch);
+        }
+        Ok((Backend::Static, main_info))
+    }
+
+    ///
+    /// The directories searched for plugins, from the highest to the lowest precedence:
+    /// the `--plugin-dir` arguments, the paths in `PLUGIN_LOADER_PATH`, the plugin folder of the config directory
+    /// and the `plugin_paths` of the core settings. Only the plugin folder of the config directory is created if it is missing.
+    ///
+    pub fn plugin_dirs() -> Result<Vec<PathBuf>, LoaderError> {
+        let gov = get_gov()?;
+        let config_dir = gov.config().config_dir()?;
+        let plugin_folder = config_dir.join("plugins");
+        fs::create_dir_all(&plugin_folder)?;
+        let mut dirs = gov.cli().plugin_dirs().iter().map(|dir| dir.to_path_buf()).collect::<Vec<_>>();
+        if let Some(paths) = env::var_os(PLUGIN_PATH_VAR) {
+            dirs.extend(env::split_paths(&paths).filter(|path| !path.as_os_str().is_empty()));
+        }
+        dirs.push(plugin_folder);
+        dirs.extend(gov.config().core_settings()?.plugin_paths().iter().map(|path| config_dir.join(path)));
+        let mut unique = Vec::with_capacity(dirs.len());
+        for dir in dirs {
+            if dir.is_dir() && !unique.contains(&dir) {
+                unique.push(dir);
+            }
+        }
+        Ok(unique)
+    }
+
+    ///
+    /// The plugin files of all search paths. A plugin found in more than one search path is only taken from the first one.
+    /// Plugins are told apart by the name in their manifest or else their file name.
+    /// The plugins linked into the loader binary follow with paths in `STATIC_PLUGIN_DIR`.
+    ///
+    #[expect(clippy::print_stderr, reason = "shadowed plugins are reported to the operator")]
+    fn plugin_files() -> Result<Vec<PathBuf>, LoaderError> {
+        let mut files = Vec::new();
+        let mut seen = Vec::<Box<str>>::new();
+        for dir in Self::plugin_dirs()? {
+            for file in plugin_files_in(&dir)? {
+                let manifest = Manifest::find(&file).ok().flatten();
+                let library_name = file.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+                let key = Box::<str>::from(manifest.as_ref().map_or(library_name, |found| found.name()));
+                if seen.contains(&key) {
+                    eprintln!("Plugin {} is shadowed by a plugin of the same name in an earlier search path", file.display());
+                    continue;
+                }
+                seen.push(key);
+                files.push(file);
+            }
+        }
+        for entry in STATIC_PLUGINS {
+            let key = Box::<str>::from(entry.name());
+            if seen.contains(&key) {
+                eprintln!("Static plugin \"{key}\" is shadowed by a plugin of the same name");
+                continue;
+            }
+            seen.push(key);
+            files.push(static_path(entry));
+        }
+        Ok(files)
+    }
+
+    pub const fn plugins(&self) -> &Plugins {
+        &self.plugins
+    }
+
+    ///
+    /// Removes the plugins with the given names.
+    ///
+    #[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+    fn remove_plugins(names: &[Box<str>]) -> Result<(), LoaderError> {
+        for name in names {
+            match Self::unload(name, RemovalReason::Failure) {
+                Ok(_) | Err(LoaderError::UnknownPlugin) => {},
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(())
+    }
+
+    ///
+    /// What happened to every plugin file at the start of the loader.
+    ///
+    pub const fn report(&self) -> &LockedVec<LoadEntry> {
+        &self.report
+    }
+
+    ///
+    /// Records in the load report that the plugins with the given names were removed again.
+    ///
+    #[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+    fn report_failure(names: &[Box<str>], error: &str) -> Result<(), LoaderError> {
+        get_gov()?.loader().report.rcu(|report| {
+            report
+                .iter()
+                .cloned()
+                .map(|mut entry| {
+                    for name in names {
+                        entry.mark_failed(name, error);
+                    }
+                    entry
+                })
+                .collect::<Vector<_>>()
+        });
+        Ok(())
+    }
+
+    ///
+    /// Unloads the plugin with the given name while the rest of the system keeps running.
+    /// Its events, endpoints and handlers are removed before the optional `plugin_unload` function of the plugin is called.
+    /// The library itself is closed or its host process killed once the returned plugin and every call still running in it are dropped.
+    ///
+    pub fn unload(name: &str, reason: RemovalReason) -> Result<Plugin, LoaderError> {
+        let plugin_id = get_gov()?
+                .loader()
+                .plugins()
+                .load()
+                .iter()
+                .find(|(_, plugin)| plugin.name() == name)
+                .map(|(id, _)| *id)
+                .ok_or(LoaderError::UnknownPlugin)?;
+        Self::unload_id(plugin_id, reason)
+    }
+
+    ///
+    /// Unloads the plugin with the given id like `unload`, which tells a plugin apart from the one replacing it during a reload.
+    ///
+    pub fn unload_id(plugin_id: Uuid, reason: RemovalReason) -> Result<Plugin, LoaderError> {
+        let plugin = cleanup::remove_plugin(plugin_id, reason)?.ok_or(LoaderError::UnknownPlugin)?;
+        match &plugin.backend {
+            Backend::Library(library) => {
+                // SAFETY:
+                // plugin_unload is an optional part of the c-api without arguments or return value.
+                // Finding the symbol implies that the library implements it according to the c-api.
+                if let Ok(unload) = unsafe { library.get::<Symbol<unsafe extern "C" fn()>>(b"plugin_unload") } {
+                    // SAFETY:
+                    // Calling plugin_unload is defined by the c-api. No other plugin can reach the plugin anymore.
+                    unsafe { unload(); }
+                }
+            },
+            // The host process may already be gone, in which case there is nothing left to unload.
+            Backend::Process(process) => process.unload().ok().unwrap_or_default(),
+            // Static plugins stay linked into the loader and have no unload function.
+            Backend::Static => {},
+            Backend::Wasm(module) => module.call(rpc::method::UNLOAD, Value::Null).map(drop).ok().unwrap_or_default(),
+        }
+        Ok(plugin)
+    }
+
+    ///
+    /// Computes the init order of the loaded plugins again, so that triggering an event doesn't have to.
+    ///
+    pub fn update_init_order(&self) {
+        let plugins = self.plugins.load();
+        let order = dependency::init_order(plugins.values().map(|plugin| (plugin.name(), plugin.dependencies())));
+        self.init_order.store(Arc::new(order));
+    }
+
+    ///
+    /// Validates the dependencies between all loaded plugins and reports every issue found.
+    /// Depending on the `DependencyPolicy` the start is aborted or the affected plugins
+    /// and all plugins depending on them are removed.
+    ///
+    #[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+    #[expect(clippy::print_stderr, reason = "dependency issues are reported to the operator")]
+    pub fn validate_dependencies() -> Result<(), LoaderError> {
+        let policy = get_gov()?.config().core_settings()?.dependency_policy();
+        let mut all_issues = Vec::new();
+        loop {
+            let issues = {
+                let gov = get_gov()?;
+                let plugins = gov.loader().plugins().load();
+                dependency::validate(plugins.values().map(|plugin| (plugin.name(), plugin.version(), plugin.dependencies())))
+            };
+            if issues.is_empty() {
+                return Ok(());
+            }
+            for issue in &issues {
+                eprintln!("Dependency issue: {issue}");
+                if policy == DependencyPolicy::Skip {
+                    Self::report_failure(issue.affected_plugins(), &issue.to_string())?;
+                }
+            }
+            let affected = issues.iter()
+                    .flat_map(DependencyIssue::affected_plugins)
+                    .cloned()
+                    .collect::<Vec<_>>();
+            all_issues.extend(issues);
+            if policy == DependencyPolicy::Abort {
+                return Err(LoaderError::Dependencies(DependencyIssues(all_issues.into())));
+            }
+            Self::remove_plugins(&affected)?;
+        }
+    }
+}
+
+#[derive(Error, Debug, Display)]
+pub enum LoaderError {
+    ApiMiscError(#[from]ApiMiscError),
+    #[display("plugin requires api version {required}, but the loader supports {oldest} to {newest}")]
+    ApiVersion {
+        newest: ApiVersion,
+        oldest: ApiVersion,
+        required: ApiVersion,
+    },
+    ConfigError(#[from]ConfigError),
+    CoreEventsMissing,
+    Dependencies(DependencyIssues),
+    Dependency(#[from]DependencyError),
+    DuplicateName,
+    Governor(#[from]GovernorError),
+    HashNotAllowed,
+    HostUnavailable,
+    IO(#[from]io::Error),
+    InvalidApiVersion,
+    #[display("{_0} invalid manifest(s)")]
+    InvalidManifests(usize),
+    InvalidName,
+    /// Host processes for isolated plugins are only supported on unix.
+    #[cfg(not(unix))]
+    IsolationUnsupported,
+    LibError(#[from]libloading::Error),
+    Manifest(#[from]ManifestError),
+    ManifestMismatch,
+    ServiceError(#[from]ServiceError),
+    SignatureInvalid,
+    SignatureMalformed,
+    SignatureMissing,
+    StaticNameMismatch,
+    TrustConfigInvalid,
+    UnknownPlugin,
+    UnknownPluginSet,
+    Wasm(#[from]wasmi::Error),
+    WasmExportMissing,
+    Watcher(#[from]notify::Error),
+    WatcherRunning,
+}
+
+///
+/// Accepts plugins requiring an api version of the same major version between `MIN_API_VERSION` and `API_VERSION`.
+///
+fn check_api_version(required: ApiVersion) -> Result<(), LoaderError> {
+    let supported = required.major == API_VERSION.major
+        && (MIN_API_VERSION.feature..=API_VERSION.feature).contains(&required.feature);
+    if !supported {
+        return Err(LoaderError::ApiVersion { newest: API_VERSION, oldest: MIN_API_VERSION, required });
+    }
+    Ok(())
+}
+
+fn check_name(name: &str) -> Result<(), LoaderError> {
+    if name.contains(':') || name == "core" {
+        return Err(LoaderError::InvalidName);
+    }
+    Ok(())
+}
+
+///
+/// Whether the file in a search path is a plugin. Hidden files and manifests never are.
+/// Other files are plugins if their extension is the one of shared libraries on this platform, "exe" or "wasm",
+/// or if their manifest declares them as executable or WebAssembly plugins, like scripts.
+///
+fn is_plugin_file(file: &Path) -> bool {
+    let hidden = file.file_name().and_then(|name| name.to_str()).is_none_or(|name| name.starts_with('.'));
+    if hidden || Manifest::is_manifest(file) {
+        return false;
+    }
+    file.extension()
+        .is_some_and(|extension| PLUGIN_EXTENSIONS.iter().any(|plugin_extension| extension == *plugin_extension))
+        || Manifest::declares_plugin(file)
+}
+
+///
+/// The plugin files directly in the directory and in its subdirectories laid out as `<name>/lib<name>.so` or `<name>/<name>.wasm`.
+/// Other files in a subdirectory, like assets or libraries the plugin links against, are left alone.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn plugin_files_in(dir: &Path) -> Result<Vec<PathBuf>, LoaderError> {
+    let mut files = Vec::new();
+    for entry in dir.read_dir()?.filter_map(Result::ok).map(|entry| entry.path()) {
+        if entry.is_file() {
+            if is_plugin_file(&entry) {
+                files.push(entry);
+            }
+            continue;
+        }
+        let Some(name) = entry.file_name().and_then(|name| name.to_str()).map(Box::<str>::from) else {
+            continue;
+        };
+        if !entry.is_dir() || name.starts_with('.') {
+            continue;
+        }
+        files.extend(entry.read_dir()?.filter_map(Result::ok).map(|nested| nested.path()).filter(|nested| {
+            let stem = nested.file_stem().and_then(|stem| stem.to_str()).unwrap_or_default();
+            nested.is_file() && stem.strip_prefix("lib").unwrap_or(stem) == &*name && is_plugin_file(nested)
+        }));
+    }
+    files.sort();
+    Ok(files)
+}
+
+///
+/// The kind of a plugin is declared by its manifest or else recognized by the extension of its file.
+/// Files with unknown extensions are loaded as native libraries.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn plugin_kind(filename: &Path, manifest: Option<&Manifest>) -> PluginKind {
+    manifest.and_then(Manifest::kind).unwrap_or_else(|| {
+        match filename.extension().and_then(|extension| extension.to_str()) {
+            Some("exe") => PluginKind::Executable,
+            Some("wasm") => PluginKind::Wasm,
+            _ => PluginKind::Library,
+        }
+    })
+}
+
+///
+/// The path standing in for the file of a plugin linked into the loader binary. Its file name is the name the plugin was registered with,
+/// so that the core settings refer to static plugins by their names like to plugins with a manifest.
+///
+fn static_path(entry: &StaticPlugin) -> PathBuf {
+    Path::new(STATIC_PLUGIN_DIR).join(entry.name())
+}
+
+///
+/// The plugin linked into the loader binary the path stands in for.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn static_plugin_at(path: &Path) -> Option<&'static StaticPlugin> {
+    STATIC_PLUGINS.iter().find(|entry| static_path(entry) == path)
+}
+
+///
+/// The names the core settings refer to a plugin by before it is loaded: the name in its manifest and the name of its file.
+///
+fn plugin_names<'names>(filename: &'names Path, manifest: Option<&'names Manifest>) -> Vec<&'names str> {
+    let library_name = filename.file_name().and_then(|name| name.to_str()).unwrap_or_default();
+    manifest.map_or_else(|| vec![library_name], |found| vec![found.name(), library_name])
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::borrow::Cow;
+    use std::{env, fs, path::Path};
+
+    use plugin_loader_api::{
+        API_VERSION, ApiVersion, ApplicationContext, PluginInfo, STATIC_PLUGINS, ServiceError,
+        pointer_traits::{EventHandlerFunc, static_plugin, trait_fn},
+    };
+    use uuid::Uuid;
+
+    use crate::config::settings::CoreSettings;
+
+    use super::{MIN_API_VERSION, STATIC_PLUGIN_DIR, check_api_version, is_plugin_file, plugin_names, static_path};
+
+    #[test]
+    fn api_versions_between_oldest_and_newest_are_accepted() {
+        let accepted = [MIN_API_VERSION, API_VERSION].map(|version| check_api_version(version).is_ok());
+        assert_eq!(accepted, [true; 2], "the oldest and the newest supported api version are accepted");
+    }
+
+    #[test]
+    fn api_versions_outside_the_range_are_rejected() {
+        let versions = [
+            ApiVersion { major: 0, feature: 1, patch: 0 },
+            ApiVersion { major: API_VERSION.major, feature: API_VERSION.feature.saturating_add(1), patch: 0 },
+            ApiVersion { major: API_VERSION.major.saturating_add(1), feature: MIN_API_VERSION.feature, patch: 0 },
+        ];
+        let accepted = versions.map(|version| check_api_version(version).is_ok());
+        assert_eq!(accepted, [false; 3], "0.1 with its smaller ApplicationContext, newer and other major api versions are rejected");
+    }
+
+    #[expect(clippy::single_call_fn, reason = "main function")]
+    #[static_plugin("static-first")]
+    fn first_main(_: Uuid) -> PluginInfo {
+        PluginInfo::new::<NoInit, _, _, _>("static-first", "1.0.0", [], API_VERSION)
+    }
+
+    #[trait_fn(EventHandlerFunc for NoInit)]
+    fn handle<'args, F: Fn() -> Result<ApplicationContext, ServiceError>, S: Into<Cow<'args, str>>>(_: F, _: S) -> Result<(), ServiceError> {
+        Ok(())
+    }
+
+    #[expect(clippy::single_call_fn, reason = "main function")]
+    #[static_plugin("static-second")]
+    fn second_main(_: Uuid) -> PluginInfo {
+        PluginInfo::new::<NoInit, _, _, _>("static-second", "1.0.0", [], API_VERSION)
+    }
+
+    #[test]
+    fn only_declared_files_next_to_a_manifest_are_plugins() {
+        let dir = env::temp_dir().join(format!("plugin-loader-{}", Uuid::new_v4()));
+        let library = format!("libfoo.{}", env::consts::DLL_EXTENSION);
+        let manifest = "api_version = \"0.2\"\nname = \"foo\"\nversion = \"1.0.0\"\n";
+        let files = [
+            (library.as_str(), ""),
+            ("libfoo.bak", ""),
+            ("libfoo.toml", manifest),
+            ("script", ""),
+            ("script.toml", &format!("{manifest}kind = \"executable\"\n")),
+        ];
+        let written = fs::create_dir_all(&dir).and_then(|()| files.iter().try_for_each(|(name, content)| fs::write(dir.join(name), content)));
+        let plugins = [library.as_str(), "libfoo.bak", "script"].map(|name| is_plugin_file(&dir.join(name)));
+        let _err = fs::remove_dir_all(&dir);
+        assert_eq!(written.ok(), Some(()), "the plugin files could be written");
+        assert_eq!(plugins, [true, false, true], "a backup of a library isn't a plugin, a script declared in its manifest is");
+    }
+
+    #[test]
+    fn static_plugins_of_one_module_are_told_apart() {
+        let paths = ["static-first", "static-second"]
+            .map(|name| STATIC_PLUGINS.iter().find(|entry| entry.name() == name).map(static_path));
+        let expected = ["static-first", "static-second"].map(|name| Some(Path::new(STATIC_PLUGIN_DIR).join(name)));
+        assert_eq!(paths, expected, "every static plugin gets a path of its own named after it");
+    }
+
+    #[test]
+    fn static_plugins_are_enabled_by_name() {
+        let settings = toml::from_str::<CoreSettings>(r#"disabled_plugins = ["static-second"]"#).ok();
+        let enabled = ["static-first", "static-second"].map(|name| {
+            let entry = STATIC_PLUGINS.iter().find(|entry| entry.name() == name)?;
+            settings.as_ref()?.is_enabled(&plugin_names(&static_path(entry), None))
+        });
+        assert_eq!(enabled, [Some(true), Some(false)], "disabled_plugins refers to static plugins by their names");
+    }
+}
@@ -0,0 +1,235 @@
+use core::time::Duration;
+#[cfg(unix)]
+use std::{fs::DirBuilder, os::unix::fs::DirBuilderExt as _};
+use std::{
+    env, fs,
+    io::{self, Read, Write},
+    path::Path,
+    process::{Child, Command, Stdio},
+    sync::Mutex,
+    thread,
+    time::Instant,
+};
+#[cfg(unix)]
+use std::os::unix::net::{UnixListener, UnixStream};
+use alloc::sync::Arc;
+
+use plugin_loader_api::ServiceError;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    governor::get_gov,
+    loader::{LoaderError, MainInfo, Plugin, reload, trust::PrivateCopy},
+    rpc::{Connection, Peer, method},
+    runtime::{
+        RuntimeError,
+        cleanup::{RemovalReason, remove_registrations},
+        event::trigger_unchecked,
+        remote,
+    },
+};
+
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(10);
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+///
+/// The host process of an isolated plugin or the process of an executable plugin.
+///
+/// The host process loads the library of the plugin instead of the loader and forwards all calls over a Unix socket.
+/// An executable plugin implements the protocol itself and exchanges its messages over stdin and stdout.
+/// A crash of the plugin only kills its process. The loader fires `core:host_exited` when that happens
+/// and respawns the process up to the `respawn_limit` of the core settings.
+/// The process is killed once the last clone of its plugin is dropped.
+///
+pub struct PluginProcess {
+    child: Mutex<Child>,
+    connection: Arc<Connection>,
+    /// The checked copy the executable was started from. Kept while the process runs, because interpreters open scripts by their path.
+    executable_copy: Option<PrivateCopy>,
+}
+
+impl PluginProcess {
+    ///
+    /// Serves the calls of the process until it closes the connection.
+    /// Calls to the process fail after the `call_timeout_ms` of the core settings.
+    ///
+    fn connect<W, R>(child: Child, writer: W, reader: R, plugin_id: Uuid) -> Self
+    where
+        W: Write + Send + 'static,
+        R: Read + Send + 'static,
+    {
+        let timeout_ms = get_gov().ok().and_then(|gov| gov.config().core_settings().ok()).unwrap_or_default().call_timeout_ms();
+        let timeout = (timeout_ms != 0).then(|| Duration::from_millis(timeout_ms));
+        let connection = Connection::new(writer, timeout, move |caller, method_name, params| {
+            let peer: Arc<dyn Peer> = Arc::<Connection>::clone(caller);
+            remote::serve(&peer, plugin_id, method_name, params)
+        });
+        let listening = Arc::clone(&connection);
+        thread::spawn(move || {
+            listening.listen(reader);
+            on_exit(plugin_id);
+        });
+        Self { child: Mutex::new(child), connection, executable_copy: None }
+    }
+
+    ///
+    /// Calls `plugin_main` of the library in the host process.
+    ///
+    pub fn main(&self, plugin_id: Uuid) -> Result<MainInfo, LoaderError> {
+        let peer: Arc<dyn Peer> = Arc::<Connection>::clone(&self.connection);
+        MainInfo::remote(peer, plugin_id)
+    }
+
+    ///
+    /// Starts a host process for the library and waits until it connected to the loader.
+    /// The socket is created in a new directory only the user of the loader can access, so that no other user can connect to it.
+    ///
+    #[cfg(unix)]
+    #[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+    pub fn spawn(library: &Path, plugin_id: Uuid) -> Result<Self, LoaderError> {
+        let socket_dir = env::temp_dir().join(format!("plugin-loader-{}", Uuid::new_v4()));
+        DirBuilder::new().mode(0o700).create(&socket_dir)?;
+        let socket_path = socket_dir.join("host.sock");
+        let connected = UnixListener::bind(&socket_path).map_err(LoaderError::from).and_then(|listener| {
+            let mut child = Command::new(env::current_exe()?)
+                .arg("--host")
+                .arg(library)
+                .arg("--host-socket")
+                .arg(&socket_path)
+                .spawn()?;
+            match accept(&listener, &mut child) {
+                Ok(stream) => Ok((child, stream)),
+                Err(error) => {
+                    kill(&mut child);
+                    Err(error)
+                }
+            }
+        });
+        fs::remove_dir_all(&socket_dir)?;
+        let (child, stream) = connected?;
+        let reader = stream.try_clone()?;
+        Ok(Self::connect(child, stream, reader, plugin_id))
+    }
+
+    ///
+    /// Isolated plugins need Unix sockets to talk to their host process.
+    ///
+    #[cfg(not(unix))]
+    pub fn spawn(_: &Path, _: Uuid) -> Result<Self, LoaderError> {
+        Err(LoaderError::IsolationUnsupported)
+    }
+
+    ///
+    /// Starts an executable plugin. It speaks the same protocol as a host process over its stdin and stdout.
+    /// The stderr of the executable is passed through to the loader.
+    /// The executable is started from the checked `copy` of it if there is one.
+    ///
+    #[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+    pub fn spawn_executable(executable: &Path, copy: Option<PrivateCopy>, plugin_id: Uuid) -> Result<Self, LoaderError> {
+        let mut child = Command::new(copy.as_ref().map_or(executable, PrivateCopy::path))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()?;
+        let (Some(stdin), Some(stdout)) = (child.stdin.take(), child.stdout.take()) else {
+            kill(&mut child);
+            return Err(LoaderError::HostUnavailable);
+        };
+        let mut process = Self::connect(child, stdin, stdout, plugin_id);
+        process.executable_copy = copy;
+        Ok(process)
+    }
+
+    ///
+    /// Calls the optional `plugin_unload` function of the library in the host process.
+    ///
+    pub fn unload(&self) -> Result<(), ServiceError> {
+        self.connection.call(method::UNLOAD, Value::Null).map(drop)
+    }
+}
+
+impl Drop for PluginProcess {
+    fn drop(&mut self) {
+        if let Ok(child) = self.child.get_mut() {
+            kill(child);
+        }
+    }
+}
+
+///
+/// Waits for the host process to connect. Fails if it exits or doesn't connect in time.
+///
+#[cfg(unix)]
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn accept(listener: &UnixListener, child: &mut Child) -> Result<UnixStream, LoaderError> {
+    listener.set_nonblocking(true)?;
+    let started = Instant::now();
+    loop {
+        match listener.accept() {
+            Ok((stream, _)) => {
+                stream.set_nonblocking(false)?;
+                return Ok(stream);
+            }
+            Err(error) if error.kind() == io::ErrorKind::WouldBlock => {}
+            Err(error) => return Err(error.into()),
+        }
+        if child.try_wait()?.is_some() || started.elapsed() > CONNECT_TIMEOUT {
+            return Err(LoaderError::HostUnavailable);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+fn kill(child: &mut Child) {
+    if child.kill().is_ok() {
+        let _status = child.wait();
+    }
+}
+
+///
+/// Called once the connection to a host process is closed.
+/// Nothing happens if the plugin was unloaded before. Otherwise the host process crashed.
+///
+#[expect(clippy::single_call_fn, reason = "function is the callback of the connection listener")]
+#[expect(clippy::print_stderr, reason = "crashed host processes are reported to the operator")]
+fn on_exit(plugin_id: Uuid) {
+    let Some(plugin) = get_gov()
+        .ok()
+        .and_then(|gov| gov.loader().plugins().load().get(&plugin_id).cloned())
+    else {
+        return;
+    };
+    eprintln!("Host process of plugin \"{}\" exited", plugin.name());
+    if let Err(error) = recover(&plugin) {
+        eprintln!("Plugin \"{}\" could not be recovered: {error}", plugin.name());
+    }
+}
+
+///
+/// Respawns the host process of a crashed plugin by reloading the plugin until the `respawn_limit` is reached.
+/// Afterwards the plugin is marked as failed and its events, endpoints and handlers are removed.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn recover(plugin: &Plugin) -> Result<(), RuntimeError> {
+    let (core_id, respawn) = {
+        let gov = get_gov()?;
+        let respawn_limit = gov.config().core_settings()?.respawn_limit();
+        let respawn = gov.loader().add_respawn(plugin.name()) <= respawn_limit;
+        (gov.runtime().core_id(), respawn)
+    };
+    trigger_unchecked(
+        core_id,
+        "core:host_exited",
+        json!({
+            "plugin": plugin.name(),
+            "respawn": respawn
+        })
+        .to_string(),
+    )?;
+    if respawn {
+        return reload::reload(plugin.name());
+    }
+    plugin.mark_failed();
+    remove_registrations(plugin.id(), RemovalReason::Failure)?;
+    Ok(())
+}
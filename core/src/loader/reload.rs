@@ -0,0 +1,161 @@
+use alloc::borrow::Cow;
+use std::path::Path;
+
+use notify::{
+    Event, EventKind, RecursiveMode, Watcher as _,
+    event::{ModifyKind, RenameMode},
+};
+use plugin_loader_api::{
+    ApplicationContext, ErrorMapper as _, ServiceError,
+    pointer_traits::{RequestHandlerFunc, trait_fn},
+};
+use serde::Deserialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    governor::get_gov,
+    loader::{Loader, LoaderError, dependency::{self, DependencyIssues}},
+    runtime::{Runtime, RuntimeError, cleanup::RemovalReason, event::init_plugin},
+};
+
+#[derive(Deserialize, PartialEq)]
+#[serde(rename_all = "lowercase")]
+enum Action {
+    Reload,
+    Unload,
+}
+
+#[derive(Deserialize)]
+struct PluginArgs {
+    action: Action,
+    name: Box<str>,
+}
+
+///
+/// Unloads or reloads the requesting plugin itself. Any plugin can request the endpoint, therefore requests naming
+/// another plugin are refused with `ServiceError::Unauthorized`. Other plugins are only replaced through the folder watcher.
+///
+#[trait_fn(RequestHandlerFunc for PluginRequestHandler)]
+pub fn handle<'args, F: Fn() -> Result<ApplicationContext, ServiceError>, S: Into<Cow<'args, str>>, T: AsRef<str>>(
+    _: F,
+    plugin_name: T,
+    args: S,
+) -> Result<String, ServiceError> {
+    let plugin_args = serde_json::from_str::<PluginArgs>(&args.into()).error(ServiceError::InvalidJson)?;
+    if *plugin_args.name != *plugin_name.as_ref() {
+        return Err(ServiceError::Unauthorized);
+    }
+    match apply(&plugin_args.action, &plugin_args.name) {
+        Err(RuntimeError::Loader(LoaderError::UnknownPlugin)) => Err(ServiceError::NotFound),
+        result => {
+            result.error(ServiceError::CoreInternalError)?;
+            Ok(json!({}).to_string())
+        }
+    }
+}
+
+fn apply(action: &Action, name: &str) -> Result<(), RuntimeError> {
+    match action {
+        Action::Reload => reload(name),
+        Action::Unload => Loader::unload(name, RemovalReason::Unload).map(drop).map_err(RuntimeError::from),
+    }
+}
+
+#[expect(clippy::single_call_fn, reason = "function is the callback of the search path watcher")]
+#[expect(clippy::print_stderr, reason = "failed reloads are reported to the operator")]
+fn on_change(result: notify::Result<Event>) {
+    let Ok(event) = result else {
+        return;
+    };
+    let action = match event.kind {
+        EventKind::Modify(ModifyKind::Name(RenameMode::To | RenameMode::Both)) => Action::Reload,
+        EventKind::Remove(_) | EventKind::Modify(ModifyKind::Name(RenameMode::From)) => Action::Unload,
+        EventKind::Any | EventKind::Access(_) | EventKind::Create(_) | EventKind::Modify(_) | EventKind::Other => return,
+    };
+    for path in &event.paths {
+        let Some(name) = plugin_at(path) else {
+            continue;
+        };
+        if let Err(error) = apply(&action, &name) {
+            eprintln!("Plugin \"{name}\" could not be updated: {error}");
+        }
+    }
+}
+
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn plugin_at(path: &Path) -> Option<Box<str>> {
+    get_gov()
+        .ok()?
+        .loader()
+        .plugins()
+        .load()
+        .values()
+        .find(|plugin| plugin.path() == path)
+        .map(|plugin| plugin.name().into())
+}
+
+///
+/// Loads the library of the plugin with the given name again while the rest of the system keeps running.
+/// The running plugin is only unloaded once the new one loaded and its dependencies are met, otherwise it keeps running.
+/// Only the reloaded plugin receives "core:init".
+///
+pub fn reload(name: &str) -> Result<(), RuntimeError> {
+    let (old_id, path) = get_gov()?
+        .loader()
+        .plugins()
+        .load()
+        .iter()
+        .find(|(_, plugin)| plugin.name() == name)
+        .map(|(id, plugin)| (*id, Box::<Path>::from(plugin.path())))
+        .ok_or(LoaderError::UnknownPlugin)?;
+    let plugin_id = load_checked(&path, Some(old_id))?;
+    Loader::unload_id(old_id, RemovalReason::Unload)?;
+    init_plugin(plugin_id, Runtime::init_arguments()?)?;
+    Ok(())
+}
+
+///
+/// Loads the library while the rest of the system keeps running and unloads it again if the dependencies of the new plugin aren't met.
+/// The plugin the library replaces, if any, doesn't count for the dependencies.
+/// Returns the id of the new plugin, which isn't initialized yet.
+///
+pub fn load_checked(path: &Path, replaces: Option<Uuid>) -> Result<Uuid, RuntimeError> {
+    // SAFETY:
+    // load_library is inherently unsafe as it calls foreign code.
+    // The only safety we have is that we trust the search paths.
+    let plugin_id = unsafe { Loader::load_library(path, replaces)? };
+    let issues = {
+        let gov = get_gov()?;
+        let plugins = gov.loader().plugins().load();
+        let new_name = plugins.get(&plugin_id).map(|plugin| Box::<str>::from(plugin.name())).ok_or(LoaderError::UnknownPlugin)?;
+        dependency::validate(
+            plugins
+                .iter()
+                .filter(|&(id, _)| Some(*id) != replaces)
+                .map(|(_, plugin)| (plugin.name(), plugin.version(), plugin.dependencies())),
+        )
+        .into_iter()
+        .filter(|issue| issue.affected_plugins().contains(&new_name))
+        .collect::<Box<[_]>>()
+    };
+    if !issues.is_empty() {
+        Loader::unload_id(plugin_id, RemovalReason::Failure)?;
+        return Err(LoaderError::Dependencies(DependencyIssues(issues)).into());
+    }
+    Ok(plugin_id)
+}
+
+///
+/// Watches the search paths and reloads a plugin when a new library is moved to the path of its library
+/// and unloads it when the library is removed. New libraries are only picked up on the next start.
+/// Libraries have to be replaced by moving the new file into place, as overwriting a loaded library is undefined.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+pub fn watch_plugins() -> Result<(), LoaderError> {
+    let mut watcher = notify::recommended_watcher(on_change)?;
+    for dir in Loader::plugin_dirs()? {
+        watcher.watch(&dir, RecursiveMode::Recursive)?;
+    }
+    get_gov()?.loader().watcher.set(watcher).map_err(|_watcher| LoaderError::WatcherRunning)
+}
@@ -0,0 +1,106 @@
+use alloc::borrow::Cow;
+use std::path::Path;
+
+use im::Vector;
+use plugin_loader_api::{
+    ApplicationContext, ErrorMapper as _, ServiceError,
+    pointer_traits::{RequestHandlerFunc, trait_fn},
+};
+use serde::Serialize;
+use serde_json::{Value, json};
+use uuid::Uuid;
+
+use crate::{
+    governor::get_gov,
+    runtime::{RuntimeError, event::trigger_unchecked},
+};
+
+///
+/// What happened to a single plugin file while the loader started.
+///
+#[derive(Serialize, Clone)]
+pub struct LoadEntry {
+    #[serde(flatten)]
+    outcome: LoadOutcome,
+    path: Box<Path>,
+}
+
+#[derive(Serialize, Clone)]
+#[serde(tag = "status", rename_all = "lowercase")]
+pub enum LoadOutcome {
+    /// Loading the file failed or the plugin was removed again because of its dependencies.
+    Failed { error: Box<str> },
+    /// The plugin was loaded under the given name and stable id.
+    Loaded { id: Uuid, name: Box<str> },
+    /// The file wasn't loaded on purpose, for example because the plugin is disabled.
+    Skipped { reason: Box<str> },
+}
+
+impl LoadEntry {
+    ///
+    /// Turns the entry of a loaded plugin into a failure if it has the given name.
+    ///
+    pub fn mark_failed(&mut self, name: &str, error: &str) {
+        if matches!(&self.outcome, LoadOutcome::Loaded { name: loaded, .. } if &**loaded == name) {
+            self.outcome = LoadOutcome::Failed { error: error.into() };
+        }
+    }
+
+    pub fn new(path: &Path, outcome: LoadOutcome) -> Self {
+        Self { outcome, path: path.into() }
+    }
+}
+
+///
+/// Replaces the outcome recorded for the plugin file at the given path, for example once a lazy plugin was activated.
+///
+pub fn record(path: &Path, outcome: &LoadOutcome) -> Result<(), RuntimeError> {
+    get_gov()?.loader().report().rcu(|report| {
+        report
+            .iter()
+            .cloned()
+            .map(|mut entry| {
+                if *entry.path == *path {
+                    entry.outcome = outcome.clone();
+                }
+                entry
+            })
+            .collect::<Vector<_>>()
+    });
+    Ok(())
+}
+
+///
+/// Prints the load report of the start and triggers `core:load_report` with it.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+#[expect(clippy::print_stderr, reason = "the load report is shown to the operator")]
+pub fn publish() -> Result<(), RuntimeError> {
+    let (core_id, entries) = {
+        let gov = get_gov()?;
+        (gov.runtime().core_id(), gov.loader().report().load_full())
+    };
+    for entry in entries.iter() {
+        match &entry.outcome {
+            LoadOutcome::Failed { error } => eprintln!("{}: failed: {error}", entry.path.display()),
+            LoadOutcome::Loaded { name, .. } => eprintln!("{}: loaded \"{name}\"", entry.path.display()),
+            LoadOutcome::Skipped { reason } => eprintln!("{}: skipped: {reason}", entry.path.display()),
+        }
+    }
+    trigger_unchecked(core_id, "core:load_report", report_json()?.to_string())?;
+    Ok(())
+}
+
+fn report_json() -> Result<Value, ServiceError> {
+    let entries = get_gov().error(ServiceError::CoreInternalError)?.loader().report().load_full();
+    Ok(json!({"plugins": serde_json::to_value(entries.iter().collect::<Vec<_>>()).error(ServiceError::CoreInternalError)?}))
+}
+
+#[trait_fn(RequestHandlerFunc for LoadReportRequestHandler)]
+pub fn handle<'args, F: Fn() -> Result<ApplicationContext, ServiceError>, S: Into<Cow<'args, str>>, T: AsRef<str>>(
+    _: F,
+    _: T,
+    _: S,
+) -> Result<String, ServiceError> {
+    Ok(report_json()?.to_string())
+}
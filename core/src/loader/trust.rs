@@ -0,0 +1,126 @@
+use std::{
+    env,
+    fs::{self, DirBuilder, OpenOptions},
+    io::Write as _,
+    path::{Path, PathBuf},
+};
+#[cfg(unix)]
+use std::os::unix::fs::{DirBuilderExt as _, OpenOptionsExt as _};
+
+use ed25519_dalek::{Signature, VerifyingKey};
+use sha2::{Digest as _, Sha256};
+use uuid::Uuid;
+
+use crate::{
+    config::settings::{TrustMode, TrustPolicy},
+    loader::LoaderError,
+};
+
+///
+/// The extension appended to the name of a plugin file to find its detached signature.
+///
+const SIGNATURE_EXTENSION: &str = "sig";
+
+///
+/// A copy of a checked plugin file in a new directory only the user of the loader can access.
+/// Native code is loaded from the copy instead of the plugin file, so that the file can't be replaced between its check and its load.
+/// The directory is removed once the copy is dropped.
+///
+pub struct PrivateCopy {
+    dir: PathBuf,
+    path: PathBuf,
+}
+
+impl PrivateCopy {
+    ///
+    /// Writes the checked content under the file name of the plugin file, so that the plugin still sees its own name.
+    ///
+    #[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+    pub fn new(file: &Path, content: &[u8]) -> Result<Self, LoaderError> {
+        let dir = env::temp_dir().join(format!("plugin-loader-{}", Uuid::new_v4()));
+        let mut dir_builder = DirBuilder::new();
+        #[cfg(unix)]
+        dir_builder.mode(0o700);
+        dir_builder.create(&dir)?;
+        let copy = Self { path: dir.join(file.file_name().unwrap_or(file.as_os_str())), dir };
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        options.mode(0o700);
+        options.open(&copy.path)?.write_all(content)?;
+        Ok(copy)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for PrivateCopy {
+    fn drop(&mut self) {
+        let _err = fs::remove_dir_all(&self.dir);
+    }
+}
+
+///
+/// Reads the plugin file once and checks it against the trust policy before any of its code is loaded.
+/// Returns the checked content, which the plugin has to be loaded from, or `None` if the policy trusts every file.
+/// Rejected files fail with a `LoaderError` naming the reason.
+///
+/// Only the plugin file itself is checked. Its `.toml` or `.json` manifest is trusted as it is: it decides how the file is
+/// loaded, but can't add code of its own, and a manifest naming another plugin than the file fails to load.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+#[expect(clippy::print_stderr, reason = "untrusted plugins loaded in developer mode are reported to the operator")]
+pub fn verify(file: &Path, policy: &TrustPolicy) -> Result<Option<Vec<u8>>, LoaderError> {
+    if policy.mode() == TrustMode::None {
+        return Ok(None);
+    }
+    let content = fs::read(file)?;
+    let result = match policy.mode() {
+        TrustMode::Hashes => verify_hash(&content, policy.allowed_hashes()),
+        TrustMode::None => Ok(()),
+        TrustMode::Signatures => verify_signature(file, &content, policy.public_keys()),
+    };
+    match result {
+        Err(error @ (LoaderError::HashNotAllowed | LoaderError::SignatureMissing)) if policy.developer_mode() => {
+            eprintln!("Loading untrusted plugin {} in developer mode: {error}", file.display());
+        }
+        checked => checked?,
+    }
+    Ok(Some(content))
+}
+
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn verify_hash(content: &[u8], allowed_hashes: &[Box<str>]) -> Result<(), LoaderError> {
+    let hash = hex::encode(Sha256::digest(content));
+    if allowed_hashes.iter().any(|allowed| allowed.eq_ignore_ascii_case(&hash)) {
+        return Ok(());
+    }
+    Err(LoaderError::HashNotAllowed)
+}
+
+///
+/// Succeeds if the detached signature of the file was made over its content by any of the public keys.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn verify_signature(file: &Path, content: &[u8], public_keys: &[Box<str>]) -> Result<(), LoaderError> {
+    let mut signature_path = file.as_os_str().to_owned();
+    signature_path.push(".");
+    signature_path.push(SIGNATURE_EXTENSION);
+    let Ok(signature_bytes) = fs::read(&signature_path) else {
+        return Err(LoaderError::SignatureMissing);
+    };
+    let signature = Signature::from_slice(&signature_bytes).map_err(|_error| LoaderError::SignatureMalformed)?;
+    for public_key in public_keys {
+        let key_bytes = hex::decode(&**public_key)
+            .ok()
+            .and_then(|bytes| <[u8; 32]>::try_from(bytes).ok())
+            .ok_or(LoaderError::TrustConfigInvalid)?;
+        let key = VerifyingKey::from_bytes(&key_bytes).map_err(|_error| LoaderError::TrustConfigInvalid)?;
+        if key.verify_strict(content, &signature).is_ok() {
+            return Ok(());
+        }
+    }
+    Err(LoaderError::SignatureInvalid)
+}
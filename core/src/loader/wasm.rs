@@ -0,0 +1,212 @@
+use alloc::sync::{Arc, Weak};
+use std::{
+    sync::Mutex,
+    thread::{self, ThreadId},
+};
+
+use plugin_loader_api::{ErrorMapper as _, ServiceError};
+use serde_json::Value;
+use uuid::Uuid;
+use wasmi::{AsContext, AsContextMut, Caller, Config, Engine, Instance, Linker, Memory, Module, Store, TypedFunc};
+
+use crate::{
+    governor::get_gov,
+    loader::LoaderError,
+    rpc::{Peer, Reply},
+    runtime::remote,
+};
+
+///
+/// The module of the imported function plugins use to call the core.
+///
+const IMPORT_MODULE: &str = "plugin_loader";
+
+///
+/// The functions a module exports to be called by the core.
+///
+#[derive(Clone, Copy)]
+struct Exports {
+    alloc: TypedFunc<i32, i32>,
+    call: TypedFunc<(i32, i32, i32, i32), i64>,
+    memory: Memory,
+}
+
+///
+/// The data of the store of a module.
+///
+struct Guest {
+    exports: Option<Exports>,
+    module: Weak<WasmModule>,
+    plugin_id: Uuid,
+}
+
+///
+/// A plugin compiled to WebAssembly and run in the embedded runtime of the loader.
+///
+/// Modules speak the same protocol as host processes, but instead of a socket all messages pass through the memory of the module.
+/// A module exports its `memory` and the functions
+/// - `plugin_alloc(len: i32) -> i32` returning a buffer of `len` bytes the core writes a method name or params into, and
+/// - `plugin_call(method_ptr: i32, method_len: i32, params_ptr: i32, params_len: i32) -> i64` serving a call of the core.
+///
+/// The core calls `main` to load the module, `handle` for its event handlers, `request` for its endpoints and `unload` before it is dropped.
+/// The module imports `plugin_loader.core_call` with the same signature as `plugin_call` to use the services of the core.
+/// Both return the pointer to a JSON reply in the upper and its length in the lower 32 bits. The reply is either
+/// `{"result": ...}` or `{"error": "<ServiceError>"}` and has to stay valid until the next call.
+///
+/// A module runs on one thread at a time. A call into a module that is still serving a call on the same thread fails with
+/// `ServiceError::PluginInternalError` instead of deadlocking. For the same reason a module can't wait for its own handlers
+/// with a blocking trigger.
+/// Every call of the core gets the `wasm_fuel` of the core settings, so that a module stuck in a loop traps with
+/// `ServiceError::PluginInternalError` instead of holding the module forever.
+///
+pub struct WasmModule {
+    entered: Mutex<Option<ThreadId>>,
+    state: Mutex<WasmState>,
+}
+
+struct WasmState {
+    exports: Exports,
+    /// The fuel the module gets for every call. `None` if fuel isn't metered.
+    fuel: Option<u64>,
+    store: Store<Guest>,
+}
+
+impl Peer for WasmModule {
+    fn call(&self, method: &str, params: Value) -> Result<Value, ServiceError> {
+        let current = thread::current().id();
+        if *self.entered.lock().error(ServiceError::CoreInternalError)? == Some(current) {
+            return Err(ServiceError::PluginInternalError);
+        }
+        let mut state = self.state.lock().error(ServiceError::CoreInternalError)?;
+        *self.entered.lock().error(ServiceError::CoreInternalError)? = Some(current);
+        let result = state.call(method, &params);
+        *self.entered.lock().error(ServiceError::CoreInternalError)? = None;
+        drop(state);
+        result
+    }
+
+    fn serves_current_thread(&self) -> bool {
+        self.entered.lock().is_ok_and(|entered| *entered == Some(thread::current().id()))
+    }
+}
+
+impl WasmModule {
+    ///
+    /// Compiles and instantiates the module. Its start function runs before the core can serve its calls
+    /// and gets the same fuel as a call.
+    ///
+    #[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+    pub fn load(wasm: &[u8], plugin_id: Uuid) -> Result<Arc<Self>, LoaderError> {
+        let wasm_fuel = get_gov().ok().and_then(|gov| gov.config().core_settings().ok()).unwrap_or_default().wasm_fuel();
+        let fuel = (wasm_fuel != 0).then_some(wasm_fuel);
+        let engine = Engine::new(Config::default().consume_fuel(fuel.is_some()));
+        let module = Module::new(&engine, wasm)?;
+        let mut store = Store::new(&engine, Guest { exports: None, module: Weak::new(), plugin_id });
+        if let Some(start_fuel) = fuel {
+            store.set_fuel(start_fuel).map_err(wasmi::Error::from)?;
+        }
+        let mut linker = Linker::new(&engine);
+        linker.func_wrap(IMPORT_MODULE, "core_call", core_call).map_err(wasmi::Error::from)?;
+        let instance = linker.instantiate(&mut store, &module)?.start(&mut store)?;
+        let exports = Exports::of(instance, &store)?;
+        let wasm_module = Arc::new(Self {
+            entered: Mutex::new(None),
+            state: Mutex::new(WasmState { exports, fuel, store }),
+        });
+        let mut state = wasm_module.state.lock().error(ServiceError::CoreInternalError)?;
+        let guest = state.store.data_mut();
+        guest.exports = Some(exports);
+        guest.module = Arc::downgrade(&wasm_module);
+        drop(state);
+        Ok(wasm_module)
+    }
+}
+
+impl Exports {
+    #[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+    fn of(instance: Instance, store: impl AsContext) -> Result<Self, LoaderError> {
+        Ok(Self {
+            alloc: instance.get_typed_func(&store, "plugin_alloc")?,
+            call: instance.get_typed_func(&store, "plugin_call")?,
+            memory: instance.get_memory(&store, "memory").ok_or(LoaderError::WasmExportMissing)?,
+        })
+    }
+}
+
+impl WasmState {
+    fn call(&mut self, method: &str, params: &Value) -> Result<Value, ServiceError> {
+        if let Some(fuel) = self.fuel {
+            self.store.set_fuel(fuel).error(ServiceError::CoreInternalError)?;
+        }
+        let params_json = serde_json::to_vec(params).error(ServiceError::CoreInternalError)?;
+        let (method_ptr, method_len) = write(&mut self.store, self.exports, method.as_bytes())?;
+        let (params_ptr, params_len) = write(&mut self.store, self.exports, &params_json)?;
+        let packed = self
+            .exports
+            .call
+            .call(&mut self.store, (method_ptr, method_len, params_ptr, params_len))
+            .error(ServiceError::PluginInternalError)?;
+        let reply = read(&self.store, self.exports.memory, packed)?;
+        serde_json::from_slice::<Reply>(&reply).error(ServiceError::PluginInternalError)?.into_result()
+    }
+}
+
+///
+/// The function a module imports to call the services of the core.
+/// Calls of the module fail with an error reply. The module traps only if the reply can't be written into its memory.
+///
+#[expect(clippy::single_call_fn, reason = "function is the callback of the module import")]
+fn core_call(
+    mut caller: Caller<'_, Guest>,
+    method_ptr: i32,
+    method_len: i32,
+    params_ptr: i32,
+    params_len: i32,
+) -> Result<i64, wasmi::Error> {
+    let Some(exports) = caller.data().exports else {
+        return Err(wasmi::Error::new("the core can't be called before the module is instantiated"));
+    };
+    let result = serve(&caller, exports.memory, (method_ptr, method_len), (params_ptr, params_len));
+    let reply = serde_json::to_vec(&Reply::from(result)).map_err(|error| wasmi::Error::new(error.to_string()))?;
+    let (reply_ptr, reply_len) =
+        write(&mut caller, exports, &reply).map_err(|error| wasmi::Error::new(error.to_string()))?;
+    Ok(pack(reply_ptr, reply_len))
+}
+
+///
+/// Reads a call of the module from its memory and serves it like the calls of host processes.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn serve(caller: &Caller<'_, Guest>, memory: Memory, method: (i32, i32), params: (i32, i32)) -> Result<Value, ServiceError> {
+    let method_name = String::from_utf8(read(caller, memory, pack(method.0, method.1))?).error(ServiceError::InvalidString)?;
+    let params_json = serde_json::from_slice(&read(caller, memory, pack(params.0, params.1))?).error(ServiceError::InvalidJson)?;
+    let peer: Arc<dyn Peer> = caller.data().module.upgrade().error(ServiceError::CoreInternalError)?;
+    remote::serve(&peer, caller.data().plugin_id, &method_name, params_json)
+}
+
+fn pack(ptr: i32, len: i32) -> i64 {
+    ((u64::from(ptr.cast_unsigned()) << u32::BITS) | u64::from(len.cast_unsigned())).cast_signed()
+}
+
+///
+/// Copies the bytes behind a packed pointer and length out of the memory of the module.
+///
+fn read(context: impl AsContext, memory: Memory, packed: i64) -> Result<Vec<u8>, ServiceError> {
+    let bits = packed.cast_unsigned();
+    let ptr = usize::try_from(bits >> u32::BITS).error(ServiceError::PluginInternalError)?;
+    let len = usize::try_from(bits & u64::from(u32::MAX)).error(ServiceError::PluginInternalError)?;
+    let mut buffer = vec![0; len];
+    memory.read(context, ptr, &mut buffer).error(ServiceError::PluginInternalError)?;
+    Ok(buffer)
+}
+
+///
+/// Copies the bytes into a buffer allocated by the module and returns the pointer and length of that buffer.
+///
+fn write(mut context: impl AsContextMut, exports: Exports, bytes: &[u8]) -> Result<(i32, i32), ServiceError> {
+    let len = i32::try_from(bytes.len()).error(ServiceError::PluginInternalError)?;
+    let ptr = exports.alloc.call(&mut context, len).error(ServiceError::PluginInternalError)?;
+    let offset = usize::try_from(ptr.cast_unsigned()).error(ServiceError::PluginInternalError)?;
+    exports.memory.write(&mut context, offset, bytes).error(ServiceError::PluginInternalError)?;
+    Ok((ptr, len))
+}
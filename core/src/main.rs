@@ -9,8 +9,10 @@ extern crate alloc;
 
 mod config;
 mod governor;
+mod host;
 mod launcher;
 mod loader;
+mod rpc;
 mod runtime;
 mod util;
 
@@ -19,6 +21,6 @@ use crate::
     launcher::{LaunchError, Launcher}
 ;
 //refactor: remove mutex blocks, check dependencies before running core:init, pointer cast in api/misc
-pub fn main() -> Result<(), LaunchError> {
+fn main() -> Result<(), LaunchError> {
     Launcher::new("example-loader").launch()
 }
@@ -0,0 +1,481 @@
+///
+/// The methods of the JSON-lines protocol between the core and a plugin running in another process.
+///
+/// Every message is a single line of JSON. Calls carry an `id`, a `method` and its `params`,
+/// responses carry the `id` of the call and either a `result` or the name of a `ServiceError` as `error`.
+///
+pub mod method {
+    /// Core to plugin: Handles a triggered event. `HandleParams` -> null.
+    pub const HANDLE: &str = "handle";
+    /// Core to plugin: Starts the plugin. `MainParams` -> `MainResult`.
+    pub const MAIN: &str = "main";
+    /// Plugin to core: `RegisterEndpointParams` -> null.
+    pub const REGISTER_ENDPOINT: &str = "register_endpoint";
+    /// Plugin to core: `RegisterEventParams` -> null.
+    pub const REGISTER_EVENT: &str = "register_event";
+    /// Plugin to core: `RegisterHandlerParams` -> null.
+    pub const REGISTER_HANDLER: &str = "register_handler";
+    /// Core to plugin: Handles a request to an endpoint. `RequestParams` -> response.
+    pub const REQUEST: &str = "request";
+    /// Plugin to core: `RequestEndpointParams` -> response.
+    pub const REQUEST_ENDPOINT: &str = "request_endpoint";
+    /// Plugin to core: `TriggerEventParams` -> null.
+    pub const TRIGGER_EVENT: &str = "trigger_event";
+    /// Plugin to core: `TriggerEventBlockingParams` -> the results of the handlers.
+    pub const TRIGGER_EVENT_BLOCKING: &str = "trigger_event_blocking";
+    /// Core to plugin: The plugin is unloaded. null -> null.
+    pub const UNLOAD: &str = "unload";
+    /// Plugin to core: `UnregisterEndpointParams` -> null.
+    pub const UNREGISTER_ENDPOINT: &str = "unregister_endpoint";
+    /// Plugin to core: `UnregisterEventParams` -> null.
+    pub const UNREGISTER_EVENT: &str = "unregister_event";
+    /// Plugin to core: `UnregisterHandlerParams` -> null.
+    pub const UNREGISTER_HANDLER: &str = "unregister_handler";
+}
+
+use alloc::sync::Arc;
+use core::{
+    num::NonZeroUsize,
+    sync::atomic::{AtomicBool, AtomicU64, Ordering},
+    time::Duration,
+};
+use std::{
+    io::{BufRead as _, BufReader, Read, Write},
+    sync::{Mutex, mpsc},
+};
+
+use plugin_loader_api::{ErrorMapper as _, PluginMetadata, ServiceError, misc::ApiMiscError};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use serde_json::Value;
+use threadpool::ThreadPool;
+use uuid::Uuid;
+
+use crate::{
+    runtime::{scheduling::DispatchMode, watchdog},
+    util::LockedMap,
+};
+
+/// The number of calls of the other end that are served at the same time. Further calls wait for a free thread.
+const MAX_CONCURRENT_CALLS: usize = 8;
+
+pub type CallHandler = Box<dyn Fn(&Arc<Connection>, &str, Value) -> Result<Value, ServiceError> + Send + Sync>;
+
+///
+/// A plugin that isn't a native library in the loader process. The loader calls it with the methods of this protocol.
+///
+pub trait Peer: Send + Sync {
+    ///
+    /// Calls the method of the plugin and waits for its result.
+    ///
+    fn call(&self, method: &str, params: Value) -> Result<Value, ServiceError>;
+
+    ///
+    /// Whether the plugin is serving a call on the current thread, so that calling it from another thread waits until that call returned.
+    ///
+    fn serves_current_thread(&self) -> bool;
+}
+
+///
+/// One end of a JSON-lines connection to another process.
+/// Both ends can call methods of the other end while they are serving a call themselves.
+///
+pub struct Connection {
+    closed: AtomicBool,
+    next_id: AtomicU64,
+    on_call: CallHandler,
+    pending: LockedMap<u64, mpsc::Sender<Result<Value, ServiceError>>>,
+    /// How long a call waits for the result of the other end. `None` waits without a limit.
+    timeout: Option<Duration>,
+    workers: ThreadPool,
+    writer: Mutex<Box<dyn Write + Send>>,
+}
+
+#[derive(Serialize, Deserialize, Default)]
+struct Message {
+    id: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    method: Option<Box<str>>,
+    #[serde(default, skip_serializing_if = "Value::is_null")]
+    params: Value,
+    #[serde(flatten)]
+    reply: Reply,
+}
+
+///
+/// The outcome of a call. Either the `result` or the name of a `ServiceError` as `error` is set.
+///
+#[derive(Serialize, Deserialize, Default)]
+pub struct Reply {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    error: Option<Box<str>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+}
+
+impl Connection {
+    ///
+    /// Reads messages until the other end closes the connection. The calls of the other end are served by a pool of
+    /// `MAX_CONCURRENT_CALLS` threads. All calls still waiting for a result fail once the connection is closed.
+    ///
+    pub fn listen<R: Read>(self: &Arc<Self>, reader: R) {
+        for line in BufReader::new(reader).lines() {
+            let Ok(json) = line else {
+                break;
+            };
+            let Ok(message) = serde_json::from_str::<Message>(&json) else {
+                continue;
+            };
+            if let Some(method) = message.method {
+                let connection = Arc::clone(self);
+                self.workers.execute(move || {
+                    let result = (connection.on_call)(&connection, &method, message.params);
+                    let response = Message { id: message.id, reply: Reply::from(result), ..Message::default() };
+                    let _err = connection.send(&response);
+                });
+                continue;
+            }
+            if let Some(sender) = self.pending.load().get(&message.id) {
+                let _err = sender.send(message.reply.into_result());
+                self.pending.rcu(|pending| pending.without(&message.id));
+            }
+        }
+        self.closed.store(true, Ordering::Relaxed);
+        for sender in self.pending.swap(Arc::default()).values() {
+            let _err = sender.send(Err(ServiceError::PluginInternalError));
+        }
+    }
+
+    ///
+    /// Creates an end of a connection writing to `writer`. Calls of the other end are served by `on_call`.
+    /// Calls to the other end fail after `timeout`. The connection only reads messages once `listen` is called.
+    ///
+    pub fn new<W, F>(writer: W, timeout: Option<Duration>, on_call: F) -> Arc<Self>
+    where
+        W: Write + Send + 'static,
+        F: Fn(&Arc<Self>, &str, Value) -> Result<Value, ServiceError> + Send + Sync + 'static,
+    {
+        Arc::new(Self {
+            closed: AtomicBool::new(false),
+            next_id: AtomicU64::new(0),
+            on_call: Box::new(on_call),
+            pending: LockedMap::default(),
+            timeout,
+            workers: ThreadPool::with_name("rpc-call".into(), MAX_CONCURRENT_CALLS),
+            writer: Mutex::new(Box::new(writer)),
+        })
+    }
+
+    fn send(&self, message: &Message) -> Result<(), ServiceError> {
+        let mut line = serde_json::to_vec(message).error(ServiceError::CoreInternalError)?;
+        line.push(b'\n');
+        let mut writer = self.writer.lock().error(ServiceError::CoreInternalError)?;
+        writer.write_all(&line).error(ServiceError::PluginInternalError)?;
+        writer.flush().error(ServiceError::PluginInternalError)
+    }
+}
+
+///
+/// Calls the method on the other end and waits for its result.
+/// Fails with `ServiceError::PluginInternalError` if the other end is gone or doesn't answer within the timeout of the connection.
+/// Long-running handlers wait for the other end without a limit.
+///
+impl Peer for Connection {
+    fn call(&self, method: &str, params: Value) -> Result<Value, ServiceError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (sender, receiver) = mpsc::channel();
+        self.pending.rcu(|pending| pending.update(id, sender.clone()));
+        if self.closed.load(Ordering::Relaxed) {
+            self.pending.rcu(|pending| pending.without(&id));
+            return Err(ServiceError::PluginInternalError);
+        }
+        let message = Message {
+            id,
+            method: Some(method.into()),
+            params,
+            ..Message::default()
+        };
+        if let Err(error) = self.send(&message) {
+            self.pending.rcu(|pending| pending.without(&id));
+            return Err(error);
+        }
+        let Some(timeout) = self.timeout else {
+            return receiver.recv().unwrap_or(Err(ServiceError::PluginInternalError));
+        };
+        loop {
+            match receiver.recv_timeout(timeout) {
+                Ok(result) => return result,
+                Err(mpsc::RecvTimeoutError::Timeout) if watchdog::is_long_running() => {}
+                Err(mpsc::RecvTimeoutError::Timeout | mpsc::RecvTimeoutError::Disconnected) => {
+                    self.pending.rcu(|pending| pending.without(&id));
+                    return Err(ServiceError::PluginInternalError);
+                }
+            }
+        }
+    }
+
+    ///
+    /// The other end serves calls on threads of its own, concurrently to the calls it makes.
+    ///
+    fn serves_current_thread(&self) -> bool {
+        false
+    }
+}
+
+impl From<Result<Value, ServiceError>> for Reply {
+    fn from(result: Result<Value, ServiceError>) -> Self {
+        match result {
+            Ok(value) => Self { error: None, result: Some(value) },
+            Err(error) => Self { error: Some(error.to_string().into()), result: None },
+        }
+    }
+}
+
+impl Reply {
+    ///
+    /// Turns the reply back into the result of the call.
+    ///
+    pub fn into_result(self) -> Result<Value, ServiceError> {
+        self.error.map_or_else(|| Ok(self.result.unwrap_or_default()), |error| Err(parse_error(&error)))
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct HandleParams {
+    pub args: Value,
+    pub handler_id: Uuid,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct MainParams {
+    pub plugin_id: Uuid,
+}
+
+///
+/// The `PluginInfo` of a plugin running in another process.
+/// The "core:init" event is delivered to the handler with the id `init_handler`.
+/// The optional fields of its `PluginDetails` are given next to the other fields.
+///
+#[derive(Serialize, Deserialize)]
+pub struct MainResult {
+    pub api_version: Box<str>,
+    #[serde(default)]
+    pub dependencies: Box<[Box<str>]>,
+    #[serde(flatten)]
+    pub details: PluginDetails,
+    pub init_handler: Uuid,
+    pub name: Box<str>,
+    pub version: Box<str>,
+}
+
+///
+/// The optional description of a plugin beyond what is needed to load it.
+///
+#[derive(Serialize, Deserialize, Default, Clone)]
+pub struct PluginDetails {
+    #[serde(default)]
+    pub authors: Box<[Box<str>]>,
+    #[serde(default)]
+    pub description: Option<Box<str>>,
+    #[serde(default)]
+    pub homepage: Option<Box<str>>,
+    #[serde(default)]
+    pub license: Option<Box<str>>,
+    #[serde(default)]
+    pub metadata: Value,
+}
+
+impl PluginDetails {
+    ///
+    /// Reads the `PluginMetadata` returned by a native library. Empty strings are left unset.
+    ///
+    pub fn from_native(native: &PluginMetadata) -> Result<Self, ServiceError> {
+        let text = |field: Result<&str, ApiMiscError>| {
+            field.error(ServiceError::InvalidString).map(|value| (!value.is_empty()).then(|| value.into()))
+        };
+        let metadata = native.metadata().error(ServiceError::InvalidString)?;
+        Ok(Self {
+            authors: native.authors().error(ServiceError::InvalidString)?.into_iter().map(Box::from).collect(),
+            description: text(native.description())?,
+            homepage: text(native.homepage())?,
+            license: text(native.license())?,
+            metadata: if metadata.is_empty() {
+                Value::Null
+            } else {
+                serde_json::from_str(metadata).error(ServiceError::InvalidJson)?
+            },
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RegisterEndpointParams {
+    pub argument_schema: Value,
+    pub endpoint_name: Box<str>,
+    pub handler_id: Uuid,
+    pub plugin_id: Uuid,
+    pub response_schema: Value,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RegisterEventParams {
+    pub argument_schema: Value,
+    /// Whether the handlers of the event run one after another or in a job each. Defaults to sequential.
+    #[serde(default)]
+    pub dispatch: DispatchMode,
+    pub event_name: Box<str>,
+    /// Limits the jobs of the event running at the same time. No limit by default.
+    #[serde(default)]
+    pub max_concurrency: Option<NonZeroUsize>,
+    pub plugin_id: Uuid,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RegisterHandlerParams {
+    pub event_name: Box<str>,
+    pub handler_id: Uuid,
+    pub plugin_id: Uuid,
+    /// Handlers with a higher priority run first. Defaults to 0.
+    #[serde(default)]
+    pub priority: i32,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RequestEndpointParams {
+    pub args: Value,
+    pub endpoint_name: Box<str>,
+    pub plugin_id: Uuid,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct RequestParams {
+    pub args: Value,
+    pub handler_id: Uuid,
+    pub plugin_name: Box<str>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TriggerEventParams {
+    pub args: Value,
+    pub event_name: Box<str>,
+    pub plugin_id: Uuid,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct TriggerEventBlockingParams {
+    pub args: Value,
+    pub event_name: Box<str>,
+    pub plugin_id: Uuid,
+    /// `None` waits until all handlers returned.
+    pub timeout_ms: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct UnregisterEndpointParams {
+    pub endpoint_name: Box<str>,
+    pub plugin_id: Uuid,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct UnregisterEventParams {
+    pub event_name: Box<str>,
+    pub plugin_id: Uuid,
+}
+
+#[derive(Serialize, Deserialize)]
+pub struct UnregisterHandlerParams {
+    pub event_name: Box<str>,
+    pub handler_id: Uuid,
+    pub plugin_id: Uuid,
+}
+
+///
+/// Deserializes the params or the result of a call.
+///
+pub fn decode<T: DeserializeOwned>(value: Value) -> Result<T, ServiceError> {
+    serde_json::from_value(value).error(ServiceError::InvalidJson)
+}
+
+///
+/// Serializes the params or the result of a call.
+///
+pub fn encode<T: Serialize>(value: T) -> Result<Value, ServiceError> {
+    serde_json::to_value(value).error(ServiceError::CoreInternalError)
+}
+
+///
+/// Parses the name of a `ServiceError` sent by the other end. Unknown names are a `ServiceError::PluginInternalError`.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn parse_error(name: &str) -> ServiceError {
+    [
+        ServiceError::CoreInternalError,
+        ServiceError::Duplicate,
+        ServiceError::InvalidApi,
+        ServiceError::InvalidJson,
+        ServiceError::InvalidSchema,
+        ServiceError::InvalidString,
+        ServiceError::NotFound,
+        ServiceError::NullFunctionPointer,
+        ServiceError::PluginUninit,
+        ServiceError::ShutingDown,
+        ServiceError::Unauthorized,
+    ]
+    .into_iter()
+    .find(|error| error.to_string() == name)
+    .unwrap_or(ServiceError::PluginInternalError)
+}
+
+#[cfg(test)]
+mod tests {
+    use plugin_loader_api::ServiceError;
+    use serde_json::{Value, json};
+
+    use super::Reply;
+
+    const ERRORS: [ServiceError; 12] = [
+        ServiceError::CoreInternalError,
+        ServiceError::Duplicate,
+        ServiceError::InvalidApi,
+        ServiceError::InvalidJson,
+        ServiceError::InvalidSchema,
+        ServiceError::InvalidString,
+        ServiceError::NotFound,
+        ServiceError::NullFunctionPointer,
+        ServiceError::PluginInternalError,
+        ServiceError::PluginUninit,
+        ServiceError::ShutingDown,
+        ServiceError::Unauthorized,
+    ];
+
+    fn receive(sent: &str) -> Option<Result<Value, String>> {
+        Some(serde_json::from_str::<Reply>(sent).ok()?.into_result().map_err(|error| error.to_string()))
+    }
+
+    fn round_trip(result: Result<Value, ServiceError>) -> Option<Result<Value, String>> {
+        receive(&serde_json::to_string(&Reply::from(result)).ok()?)
+    }
+
+    #[test]
+    fn errors_round_trip() {
+        for error in ERRORS {
+            assert_eq!(round_trip(Err(error)), Some(Err(error.to_string())), "every error is parsed back from its name");
+        }
+    }
+
+    #[test]
+    fn results_round_trip() {
+        let result = json!({"answer": 42i32, "list": ["a", "b"]});
+        assert_eq!(round_trip(Ok(result.clone())), Some(Ok(result)), "the result survives a reply");
+        assert_eq!(round_trip(Ok(Value::Null)), Some(Ok(Value::Null)), "a null result survives a reply");
+        assert_eq!(receive("{}"), Some(Ok(Value::Null)), "a missing result is null");
+    }
+
+    #[test]
+    fn unknown_error_is_plugin_internal_error() {
+        assert_eq!(
+            receive(r#"{"error": "NoSuchError"}"#),
+            Some(Err(ServiceError::PluginInternalError.to_string())),
+            "unknown errors of the other end are internal errors of the plugin"
+        );
+    }
+}
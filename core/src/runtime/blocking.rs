@@ -0,0 +1,109 @@
+use core::time::Duration;
+use std::time::Instant;
+
+use plugin_loader_api::{
+    ErrorMapper as _, ServiceError,
+    pointer_traits::{EventTriggerBlockingService, trait_fn},
+};
+use serde::Serialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    governor::get_gov,
+    runtime::{
+        PowerState,
+        event,
+        scheduling::{self, Completion},
+    },
+};
+
+use ServiceError::CoreInternalError;
+
+///
+/// The result of a single handler of an event triggered with `trigger_event_blocking`.
+///
+#[derive(Serialize)]
+struct HandlerResult {
+    /// The name of the `ServiceError` of a failed handler.
+    error: Option<Box<str>>,
+    /// The id of the plugin owning the handler, which identifies the handler among the handlers of the event.
+    #[serde(skip)]
+    owner_id: Uuid,
+    plugin: Box<str>,
+    status: HandlerStatus,
+}
+
+#[derive(Clone, Copy, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum HandlerStatus {
+    /// The handler returned an error or panicked.
+    Failed,
+    /// The handler returned successfully.
+    Finished,
+    /// The handler didn't return before the timeout. It keeps running in the background.
+    Pending,
+    /// The handler wasn't called because its plugin failed.
+    Skipped,
+}
+
+///
+/// Triggers an event of the requesting plugin and waits until all handlers returned or the timeout passed.
+/// Responds with the result of every handler in the order the handlers run.
+///
+#[trait_fn(EventTriggerBlockingService for EventTriggerBlocking)]
+pub(super) fn trigger_blocking<S: AsRef<str>, T: AsRef<str>>(
+    plugin_id: Uuid,
+    event_name: S,
+    args: T,
+    timeout: Option<Duration>,
+) -> Result<String, ServiceError> {
+    match get_gov().error(CoreInternalError)?.runtime().check_power() {
+        PowerState::Shutdown | PowerState::Restart => return Err(ServiceError::ShutingDown),
+        PowerState::Running | PowerState::Cancel => {}
+    }
+    let results = trigger_and_wait(plugin_id, event_name.as_ref(), args.as_ref().to_owned(), timeout)?;
+    Ok(json!({"results": results}).to_string())
+}
+
+///
+/// Schedules the handlers like `trigger` does, so that priorities, the dispatch of the event and long-running handlers apply,
+/// and waits for their outcomes. Handlers that didn't return before the timeout are reported as pending.
+/// Handlers of a plugin that serves a call on the current thread, like a WebAssembly module triggering the event,
+/// could only run after the wait. The trigger fails with `ServiceError::Unauthorized` instead of waiting for them.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn trigger_and_wait(
+    plugin_id: Uuid,
+    event_name: &str,
+    args: String,
+    timeout: Option<Duration>,
+) -> Result<Vec<HandlerResult>, ServiceError> {
+    let calls = event::checked_calls(plugin_id, event_name, &args)?;
+    if calls.iter().any(|(stored_handler, _)| stored_handler.handler().is_held_by_current_thread()) {
+        return Err(ServiceError::Unauthorized);
+    }
+    let mut results = calls
+        .iter()
+        .map(|(stored_handler, plugin)| HandlerResult {
+            error: None,
+            owner_id: stored_handler.plugin_id(),
+            plugin: event::owner_name(stored_handler.handler(), plugin.as_ref()),
+            status: HandlerStatus::Pending,
+        })
+        .collect::<Vec<_>>();
+    let completion = Completion::new(calls.len());
+    scheduling::schedule(event_name, calls, args, Some(&completion))?;
+    let outcomes = completion.wait(timeout.and_then(|duration| Instant::now().checked_add(duration)))?;
+    for result in &mut results {
+        let Some(outcome) = outcomes.get(&result.owner_id) else {
+            continue;
+        };
+        (result.status, result.error) = match outcome {
+            Some(Ok(())) => (HandlerStatus::Finished, None),
+            Some(Err(error)) => (HandlerStatus::Failed, Some(error.to_string().into())),
+            None => (HandlerStatus::Skipped, None),
+        };
+    }
+    Ok(results)
+}
@@ -0,0 +1,135 @@
+use core::cmp::Reverse;
+
+use im::HashMap;
+use plugin_loader_api::{ErrorMapper as _, ServiceError};
+use serde::Serialize;
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    governor::get_gov,
+    loader::Plugin,
+    runtime::event::trigger_unchecked,
+};
+
+use ServiceError::CoreInternalError;
+
+///
+/// Why events or endpoints of a plugin are removed.
+///
+#[derive(Serialize, Clone, Copy, PartialEq, Eq, Debug)]
+#[serde(rename_all = "lowercase")]
+pub enum RemovalReason {
+    /// The plugin failed, e.g. because its dependencies aren't met.
+    Failure,
+    /// The loader shuts down or restarts.
+    Shutdown,
+    /// The plugin is unloaded or reloaded while the rest of the system keeps running.
+    Unload,
+    /// The plugin unregistered them itself.
+    Unregister,
+}
+
+///
+/// Removes everything the plugin with the given id owns in one step.
+/// Its events, its endpoints, its handlers on events of other plugins and the plugin itself are removed
+/// before the remaining plugins are informed with "core:unregister".
+/// Returns the removed plugin, which keeps its library loaded until it is dropped.
+///
+pub fn remove_plugin(plugin_id: Uuid, reason: RemovalReason) -> Result<Option<Plugin>, ServiceError> {
+    remove_registrations(plugin_id, reason)?;
+    let gov = get_gov().error(CoreInternalError)?;
+    let plugin = gov.loader().plugins().load().get(&plugin_id).cloned();
+    gov.loader().plugins().rcu(|plugins| plugins.without(&plugin_id));
+    gov.loader().update_init_order();
+    Ok(plugin)
+}
+
+///
+/// Removes the events, the endpoints and the handlers of the plugin with the given id but keeps the plugin loaded.
+/// The remaining plugins are informed with "core:unregister".
+///
+pub fn remove_registrations(plugin_id: Uuid, reason: RemovalReason) -> Result<(), ServiceError> {
+    let (plugin_name, event_names, endpoint_names) = {
+        let gov = get_gov().error(CoreInternalError)?;
+        let plugin_name = gov.loader().plugins().load().get(&plugin_id).map(|plugin| Box::<str>::from(plugin.name()));
+        let event_names = owned_names(&gov.events().load(), |event| event.plugin_id() == plugin_id);
+        let endpoint_names = owned_names(&gov.endpoints().load(), |endpoint| endpoint.plugin_id() == plugin_id);
+        gov.events().rcu(|events| {
+            events.iter()
+                .filter(|(_, event)| event.plugin_id() != plugin_id)
+                .map(|(event_name, event)| {
+                    let mut owned_event = event.clone();
+                    owned_event.handlers_mut().retain(|handler| handler.plugin_id() != plugin_id);
+                    (event_name.clone(), owned_event)
+                })
+                .collect::<HashMap<_, _>>()
+        });
+        gov.endpoints().rcu(|endpoints| {
+            endpoints.iter()
+                .filter(|(_, endpoint)| endpoint.plugin_id() != plugin_id)
+                .map(|(endpoint_name, endpoint)| (endpoint_name.clone(), endpoint.clone()))
+                .collect::<HashMap<_, _>>()
+        });
+        (plugin_name, event_names, endpoint_names)
+    };
+    if let Some(name) = plugin_name {
+        notify_unregister(&name, reason, &event_names, &endpoint_names)?;
+    }
+    Ok(())
+}
+
+///
+/// Removes all plugins in the reverse order of their init, so that plugins are removed before their dependencies.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+pub fn remove_all_plugins(reason: RemovalReason) -> Result<(), ServiceError> {
+    let gov = get_gov().error(CoreInternalError)?;
+    let order = removal_order(&gov.loader().plugins().load(), &gov.loader().init_order().load());
+    drop(gov);
+    for plugin_id in order {
+        remove_plugin(plugin_id, reason)?;
+    }
+    Ok(())
+}
+
+///
+/// Informs the remaining plugins that events or endpoints of the given plugin were removed.
+/// The notification is sent while shutting down as well.
+///
+pub fn notify_unregister(
+    plugin_name: &str,
+    reason: RemovalReason,
+    events: &[Box<str>],
+    endpoints: &[Box<str>],
+) -> Result<(), ServiceError> {
+    let core_id = get_gov().error(CoreInternalError)?.runtime().core_id();
+    trigger_unchecked(
+        core_id,
+        "core:unregister",
+        json!({
+            "plugin": plugin_name,
+            "reason": reason,
+            "events": events,
+            "endpoints": endpoints
+        })
+        .to_string(),
+    )
+}
+
+pub fn owned_names<V, F: Fn(&V) -> bool>(map: &HashMap<Box<str>, V>, is_owned: F) -> Box<[Box<str>]> {
+    map.iter()
+        .filter(|(_, value)| is_owned(value))
+        .map(|(name, _)| name.clone())
+        .collect()
+}
+
+///
+/// Orders the plugins reversed to their init order. Plugins without a position in the init order are removed first, ordered by their names.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn removal_order(plugins: &HashMap<Uuid, Plugin>, init_order: &HashMap<Box<str>, usize>) -> Vec<Uuid> {
+    let mut ordered = plugins.values().collect::<Vec<_>>();
+    ordered.sort_by_key(|plugin| (init_order.get(plugin.name()).map(Reverse), plugin.name()));
+    ordered.into_iter().map(Plugin::id).collect()
+}
@@ -1,6 +1,6 @@
 use core::time::Duration;
 use std::thread;
-use alloc::borrow::Cow;
+use alloc::{borrow::Cow, sync::Arc};
 use chrono::{SecondsFormat, Utc};
 use plugin_loader_api::{
     ApplicationContext, ErrorMapper as _, ServiceError,
@@ -18,7 +18,7 @@ use serde_json::json;
 use uuid::Uuid;
 
 use crate::{
-    config::ConfigRequestHandler, governor::get_gov, loader::Plugin, runtime::{ContextSupplierImpl, EventTrigger, PowerState, RuntimeError, schema_from_file}, util::LockedMap
+    config::ConfigRequestHandler, governor::get_gov, loader::{Plugin, lazy, reload::PluginRequestHandler, report::LoadReportRequestHandler}, rpc::{self, Peer, RequestParams, method}, runtime::{ContextSupplierImpl, EventTrigger, PowerState, RuntimeError, cleanup::{RemovalReason, notify_unregister}, event::HandlerPriorityRequestHandler, fault, registry::PluginsRequestHandler, scheduling::EventDispatchRequestHandler, schema_from_file, watchdog::{LongRunningRequestHandler, RunningHandlersRequestHandler}}, util::LockedMap
 };
 
 use ServiceError::CoreInternalError;
@@ -29,13 +29,43 @@ pub type Endpoints = LockedMap<Box<str>, Endpoint>;
 pub struct Endpoint {
     argument_validator: Validator,
     plugin_id: Uuid,
-    request_handler: RequestHandlerFuncUnsafeFP,
+    request_handler: RequestHandler,
     response_validator: Validator,
 }
 
+///
+/// The function behind a registered endpoint.
+///
+#[derive(Clone)]
+pub enum RequestHandler {
+    /// A placeholder of a lazy plugin. The plugin is activated and the request forwarded to its handler on the first request.
+    Lazy { plugin_name: Box<str> },
+    /// A handler of a library loaded into the core.
+    Native(RequestHandlerFuncUnsafeFP),
+    /// A handler of a plugin that isn't a native library in the loader process. It is called through the peer of that plugin.
+    Remote {
+        handler_id: Uuid,
+        peer: Arc<dyn Peer>,
+    },
+}
+
+impl RequestHandler {
+    fn request(&self, plugin_name: String, args: Cow<'_, str>) -> Result<String, ServiceError> {
+        match self {
+            Self::Lazy { .. } => Err(ServiceError::NotFound),
+            Self::Native(handler) => handler.to_safe_fp()(ContextSupplierImpl, plugin_name, args),
+            Self::Remote { handler_id, peer } => {
+                let args_json = serde_json::from_str(&args).error(ServiceError::InvalidJson)?;
+                let params = RequestParams { args: args_json, handler_id: *handler_id, plugin_name: plugin_name.into() };
+                Ok(peer.call(method::REQUEST, rpc::encode(params)?)?.to_string())
+            }
+        }
+    }
+}
+
 impl Endpoint {
-    fn new(
-        request_handler: RequestHandlerFuncUnsafeFP,
+    const fn new(
+        request_handler: RequestHandler,
         argument_validator: Validator,
         response_validator: Validator,
         plugin_id: Uuid,
@@ -47,6 +77,10 @@ impl Endpoint {
             response_validator,
         }
     }
+
+    pub const fn plugin_id(&self) -> Uuid {
+        self.plugin_id
+    }
 }
 
 #[derive(Deserialize, Serialize, PartialEq)]
@@ -70,7 +104,7 @@ pub fn register_core_endpoints(endpoints: &Endpoints, core_id: Uuid) -> Result<(
     new_endpoints.insert(
         "core:power".into(),
         Endpoint::new(
-            CorePowerHandler::c_handle_fp(),
+            RequestHandler::Native(CorePowerHandler::c_handle_fp()),
             schema_from_file(include_str!("../../endpoint/power-args.json"))?,
             schema_from_file(include_str!("../../endpoint/power-resp.json"))?,
             core_id
@@ -79,12 +113,75 @@ pub fn register_core_endpoints(endpoints: &Endpoints, core_id: Uuid) -> Result<(
     new_endpoints.insert(
         "core:config".into(),
         Endpoint::new(
-            ConfigRequestHandler::c_handle_fp(),
+            RequestHandler::Native(ConfigRequestHandler::c_handle_fp()),
             schema_from_file(include_str!("../../endpoint/config-args.json"))?,
             schema_from_file(include_str!("../../endpoint/config-resp.json"))?,
             core_id
         )
     );
+    new_endpoints.insert(
+        "core:handler_priority".into(),
+        Endpoint::new(
+            RequestHandler::Native(HandlerPriorityRequestHandler::c_handle_fp()),
+            schema_from_file(include_str!("../../endpoint/handler-priority-args.json"))?,
+            schema_from_file(include_str!("../../endpoint/handler-priority-resp.json"))?,
+            core_id
+        )
+    );
+    new_endpoints.insert(
+        "core:load_report".into(),
+        Endpoint::new(
+            RequestHandler::Native(LoadReportRequestHandler::c_handle_fp()),
+            schema_from_file(include_str!("../../endpoint/load-report-args.json"))?,
+            schema_from_file(include_str!("../../endpoint/load-report-resp.json"))?,
+            core_id
+        )
+    );
+    new_endpoints.insert(
+        "core:plugins".into(),
+        Endpoint::new(
+            RequestHandler::Native(PluginsRequestHandler::c_handle_fp()),
+            schema_from_file(include_str!("../../endpoint/plugins-args.json"))?,
+            schema_from_file(include_str!("../../endpoint/plugins-resp.json"))?,
+            core_id
+        )
+    );
+    new_endpoints.insert(
+        "core:event_dispatch".into(),
+        Endpoint::new(
+            RequestHandler::Native(EventDispatchRequestHandler::c_handle_fp()),
+            schema_from_file(include_str!("../../endpoint/event-dispatch-args.json"))?,
+            schema_from_file(include_str!("../../endpoint/event-dispatch-resp.json"))?,
+            core_id
+        )
+    );
+    new_endpoints.insert(
+        "core:long_running".into(),
+        Endpoint::new(
+            RequestHandler::Native(LongRunningRequestHandler::c_handle_fp()),
+            schema_from_file(include_str!("../../endpoint/long-running-args.json"))?,
+            schema_from_file(include_str!("../../endpoint/long-running-resp.json"))?,
+            core_id
+        )
+    );
+    new_endpoints.insert(
+        "core:running_handlers".into(),
+        Endpoint::new(
+            RequestHandler::Native(RunningHandlersRequestHandler::c_handle_fp()),
+            schema_from_file(include_str!("../../endpoint/running-handlers-args.json"))?,
+            schema_from_file(include_str!("../../endpoint/running-handlers-resp.json"))?,
+            core_id
+        )
+    );
+    new_endpoints.insert(
+        "core:plugin".into(),
+        Endpoint::new(
+            RequestHandler::Native(PluginRequestHandler::c_handle_fp()),
+            schema_from_file(include_str!("../../endpoint/plugin-args.json"))?,
+            schema_from_file(include_str!("../../endpoint/plugin-resp.json"))?,
+            core_id
+        )
+    );
     endpoints.rcu(|map| HashMap::clone(map).union(new_endpoints.clone()));
     Ok(())
 }
@@ -154,24 +251,42 @@ pub(super) fn register<S: AsRef<str>, T: AsRef<str>, Q: AsRef<str>>(
     endpoint_name: Q,
     handler: RequestHandlerFuncUnsafeFP,
 ) -> Result<(), ServiceError> {
-    if endpoint_name.as_ref().contains(':') {
+    insert(args_schema.as_ref(), response_schema.as_ref(), plugin_id, endpoint_name.as_ref(), RequestHandler::Native(handler))
+}
+
+///
+/// Registers the endpoint of the given plugin.
+/// Endpoints of plugins running in a process of their own are registered through this function as well.
+///
+pub fn insert(
+    args_schema: &str,
+    response_schema: &str,
+    plugin_id: Uuid,
+    endpoint_name: &str,
+    handler: RequestHandler,
+) -> Result<(), ServiceError> {
+    if endpoint_name.contains(':') {
         return Err(ServiceError::InvalidString);
     }
-    let argument_schema_json = serde_json::from_str(args_schema.as_ref()).error(ServiceError::InvalidJson)?;
+    let argument_schema_json = serde_json::from_str(args_schema).error(ServiceError::InvalidJson)?;
     let argument_validator =
         jsonschema::validator_for(&argument_schema_json).error(ServiceError::InvalidSchema)?;
-    let response_schema_json = serde_json::from_str(response_schema.as_ref()).error(ServiceError::InvalidJson)?;
+    let response_schema_json = serde_json::from_str(response_schema).error(ServiceError::InvalidJson)?;
     let response_validator =
         jsonschema::validator_for(&response_schema_json).error(ServiceError::InvalidSchema)?;
     let endpoint = Endpoint::new(handler, argument_validator, response_validator, plugin_id);
     let full_name = {
         let gov = get_gov().error(CoreInternalError)?;
         let plugins = gov.loader().plugins().load();
-        let plugin_name = plugins.get(&plugin_id).map(Plugin::name).error(ServiceError::NotFound)?;
-        if gov.endpoints().load().contains_key(endpoint_name.as_ref()) {
+        let plugin_name = plugins
+                .get(&plugin_id)
+                .filter(|plugin| !plugin.is_failed())
+                .map(Plugin::name)
+                .error(ServiceError::NotFound)?;
+        if gov.endpoints().load().contains_key(endpoint_name) {
             return Err(ServiceError::Duplicate);
         }
-        let full_name = format!("{}:{}", plugin_name, endpoint_name.as_ref());
+        let full_name = format!("{plugin_name}:{endpoint_name}");
         gov.endpoints()
             .rcu(|map| map.update(full_name.clone().into(), endpoint.clone()));
         full_name
@@ -191,12 +306,37 @@ pub(super) fn register<S: AsRef<str>, T: AsRef<str>, Q: AsRef<str>>(
     Ok(())
 }
 
+///
+/// Registers a placeholder for an endpoint of a lazy plugin that accepts any arguments and responses.
+/// No `core:endpoint` event is triggered as the schemas are only known once the plugin registers the endpoint itself.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+pub fn insert_placeholder(plugin_name: &str, endpoint_name: &str, placeholder_id: Uuid) -> Result<(), ServiceError> {
+    if endpoint_name.contains(':') {
+        return Err(ServiceError::InvalidString);
+    }
+    let validator = jsonschema::validator_for(&json!(true)).error(ServiceError::InvalidSchema)?;
+    let endpoint = Endpoint::new(
+        RequestHandler::Lazy { plugin_name: plugin_name.into() },
+        validator.clone(),
+        validator,
+        placeholder_id,
+    );
+    let full_name: Box<str> = format!("{plugin_name}:{endpoint_name}").into();
+    let gov = get_gov().error(CoreInternalError)?;
+    if gov.endpoints().load().contains_key(&full_name) {
+        return Err(ServiceError::Duplicate);
+    }
+    gov.endpoints().rcu(|map| map.update(full_name.clone(), endpoint.clone()));
+    Ok(())
+}
+
 #[trait_fn(EndpointUnregisterService for EndpointUnregister)]
 pub(super) fn unregister<S: AsRef<str>>(
     plugin_id: Uuid,
     endpoint_name: S,
 ) -> Result<(), ServiceError> {
-    {
+    let plugin_name = {
         let gov = get_gov().error(CoreInternalError)?;
         let endpoints = gov.endpoints().load();
         let endpoint = endpoints
@@ -208,9 +348,11 @@ pub(super) fn unregister<S: AsRef<str>>(
 
         gov.endpoints()
             .rcu(|map| map.without(endpoint_name.as_ref()));
-    }
+        let plugins = gov.loader().plugins().load();
+        plugins.get(&plugin_id).map(Plugin::name).error(ServiceError::NotFound)?.to_owned()
+    };
 
-    Ok(())
+    notify_unregister(&plugin_name, RemovalReason::Unregister, &[], &[endpoint_name.as_ref().into()])
 }
 
 #[trait_fn(EndpointRequestService for EndpointRequest)]
@@ -229,7 +371,7 @@ pub(super) fn request<'args, S: AsRef<str>, T: Into<Cow<'args, str>>>(
                 .error(ServiceError::NotFound)?
                 .to_owned()
     };
-    let handler = {
+    let (handler, plugin) = {
         let gov = get_gov().error(CoreInternalError)?;
         let endpoints = gov.endpoints().load();
         let endpoint = endpoints
@@ -239,10 +381,28 @@ pub(super) fn request<'args, S: AsRef<str>, T: Into<Cow<'args, str>>>(
             .argument_validator
             .validate(&arguments_json)
             .error(ServiceError::InvalidApi)?;
-        endpoint.request_handler.to_safe_fp()
+        let plugin = gov.loader().plugins().load().get(&endpoint.plugin_id).cloned();
+        (endpoint.request_handler.clone(), plugin)
     };
-    let response = handler(ContextSupplierImpl, plugin_name, cow_args)?;
+    if let RequestHandler::Lazy { plugin_name: lazy_name } = handler {
+        lazy::activate(&lazy_name).error(ServiceError::PluginInternalError)?;
+        return EndpointRequest::request(endpoint_name, plugin_id, cow_args);
+    }
+    let response = handler.request(plugin_name, cow_args);
+    if let (Err(error), Some(faulted)) = (response.as_ref(), plugin.as_ref())
+        && fault::is_crash(*error)
+    {
+        fault::record(faulted, *error);
+    }
+    drop(plugin);
+    check_response(endpoint_name.as_ref(), response?)
+}
 
+///
+/// Validates the response of the handler of an endpoint against the response schema of the endpoint.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn check_response(endpoint_name: &str, response: String) -> Result<String, ServiceError> {
     let response_json =
         serde_json::from_str(&response).error(ServiceError::InvalidJson)?;
 
@@ -250,7 +410,7 @@ pub(super) fn request<'args, S: AsRef<str>, T: Into<Cow<'args, str>>>(
         let gov = get_gov().error(CoreInternalError)?;
         let endpoints = gov.endpoints().load();
         let endpoint = endpoints
-            .get(endpoint_name.as_ref())
+            .get(endpoint_name)
             .error(ServiceError::NotFound)?;
         endpoint
             .response_validator
@@ -1,38 +1,74 @@
-use std::{collections::HashSet};
+use alloc::{
+    borrow::Cow,
+    collections::VecDeque,
+    sync::Arc,
+};
+use core::{cmp::Reverse, mem, num::NonZeroUsize, sync::atomic::{AtomicU64, Ordering}};
+use std::{collections::HashSet, sync::Mutex, thread};
 use core::hash::{Hash, Hasher};
 use plugin_loader_api::{
-    ErrorMapper as _, EventHandler, ServiceError,
+    ApplicationContext, ErrorMapper as _, EventDispatch, EventHandler, ServiceError,
     pointer_traits::{
         EventHandlerFuncUnsafeFP, EventHandlerRegisterService,
         EventHandlerUnregisterService, EventRegisterService, EventTriggerService,
-        EventUnregisterService, trait_fn,
+        EventUnregisterService, RequestHandlerFunc, trait_fn,
     },
 };
 use im::HashMap;
 use jsonschema::Validator;
+use serde::Deserialize;
 use serde_json::json;
-use topo_sort::TopoSort;
 use uuid::Uuid;
 
 use crate::{
     governor::get_gov,
-    loader::Plugin,
-    runtime::{ContextSupplierImpl, PowerState, RuntimeError, schema_from_file},
+    loader::{Plugin, lazy},
+    rpc::{self, HandleParams, Peer, method},
+    runtime::{
+        ContextSupplierImpl, PowerState, RuntimeError,
+        cleanup::{RemovalReason, notify_unregister},
+        fault,
+        scheduling::{self, Completion, Dispatch},
+        schema_from_file,
+        watchdog::{self, LONG_RUNNING_THREAD_NAME},
+    },
     util::{ArcMapExt as _, LockedMap, TrueOrErr as _},
 };
 
 use ServiceError::CoreInternalError;
 
+///
+/// The number of handlers registered since the start of the loader.
+///
+static REGISTRATIONS: AtomicU64 = AtomicU64::new(0);
+
 pub type Events = LockedMap<Box<str>, Event>;
+pub type HandlerCall = (StoredEventHandler, Option<Plugin>);
+pub type HandlerCalls = Vec<HandlerCall>;
+
+#[derive(Deserialize)]
+struct HandlerPriorityArgs {
+    handler_id: Uuid,
+    priority: i32,
+}
 
 #[derive(Clone)]
 pub struct Event {
     argument_validator: Validator,
+    dispatch: Dispatch,
     handlers: HashSet<StoredEventHandler>,
     plugin_id: Uuid,
 }
 
 impl Event {
+    pub const fn dispatch(&self) -> &Dispatch {
+        &self.dispatch
+    }
+
+    pub const fn handlers(&self) -> &HashSet<StoredEventHandler> {
+        &self.handlers
+    }
+
     pub const fn handlers_mut(&mut self) -> &mut HashSet<StoredEventHandler> {
         &mut self.handlers
     }
@@ -41,16 +77,135 @@ impl Event {
         Self {
             handlers: HashSet::new(),
             argument_validator,
+            dispatch: Dispatch::default(),
             plugin_id,
         }
     }
 
+    pub const fn plugin_id(&self) -> Uuid {
+        self.plugin_id
+    }
+
+    pub fn set_dispatch(&mut self, dispatch: Dispatch) {
+        self.dispatch = dispatch;
+    }
+
+}
+
+///
+/// The function behind a registered event handler.
+///
+#[derive(Clone)]
+pub enum Handler {
+    /// A placeholder of a lazy plugin. The plugin is activated and the event forwarded to its handlers when the event is triggered.
+    Lazy {
+        event_name: Box<str>,
+        placeholder_id: Uuid,
+        plugin_name: Box<str>,
+    },
+    /// A handler of a library loaded into the core.
+    Native(EventHandler),
+    /// A handler of a plugin that isn't a native library in the loader process. It is called through the peer of that plugin.
+    Remote {
+        handler_id: Uuid,
+        peer: Arc<dyn Peer>,
+    },
+}
+
+impl Handler {
+    fn handle(&self, args: &str) -> Result<(), ServiceError> {
+        match self {
+            Self::Lazy { event_name, plugin_name, .. } => {
+                let plugin_id = lazy::activate(plugin_name).error(ServiceError::PluginInternalError)?;
+                owned_calls(plugin_id, event_name)?
+                    .iter()
+                    .filter_map(|(stored_handler, plugin)| call(event_name, &stored_handler.handler, plugin.as_ref(), args, None))
+                    .collect()
+            }
+            Self::Native(handler) => handler.handle(ContextSupplierImpl, args),
+            Self::Remote { handler_id, peer } => {
+                let args_json = serde_json::from_str(args).error(ServiceError::InvalidJson)?;
+                let params = rpc::encode(HandleParams { args: args_json, handler_id: *handler_id })?;
+                peer.call(method::HANDLE, params).map(drop)
+            }
+        }
+    }
+
+    pub const fn id(&self) -> Uuid {
+        match self {
+            Self::Lazy { placeholder_id, .. } => *placeholder_id,
+            Self::Native(handler) => handler.id(),
+            Self::Remote { handler_id, .. } => *handler_id,
+        }
+    }
+
+    ///
+    /// Whether the handler can't be called before the current thread returned, because its plugin is serving a call on it.
+    ///
+    pub fn is_held_by_current_thread(&self) -> bool {
+        matches!(self, Self::Remote { peer, .. } if peer.serves_current_thread())
+    }
+}
+
+///
+/// The handlers of a trigger that weren't called yet.
+///
+#[derive(Clone)]
+pub struct PendingCalls {
+    args: Arc<str>,
+    completion: Option<Arc<Completion>>,
+    event_name: Arc<str>,
+    queue: Arc<Mutex<PendingQueue>>,
+}
+
+impl PendingCalls {
+    fn next(&self) -> Option<HandlerCall> {
+        let mut queue = self.queue.lock().ok()?;
+        let next = queue.calls.pop_front()?;
+        queue.running = Some(next.0.plugin_id);
+        drop(queue);
+        Some(next)
+    }
+
+    ///
+    /// Moves the handlers that weren't called yet to a new job on the event pool.
+    /// Used when the running handler is detached from the event pool, so that the handlers after it don't wait for it.
+    ///
+    pub fn resume_elsewhere(&self) -> Result<(), ServiceError> {
+        let (running, remaining) = {
+            let mut queue = self.queue.lock().error(CoreInternalError)?;
+            (queue.running, mem::take(&mut queue.calls))
+        };
+        if let (Some(completion), Some(owner_id)) = (&self.completion, running) {
+            completion.detach(owner_id);
+        }
+        if remaining.is_empty() {
+            return Ok(());
+        }
+        run_on_pool(&self.event_name, remaining.into(), self.args.to_string(), self.completion.clone())
+    }
+}
+
+#[derive(Default)]
+struct PendingQueue {
+    calls: VecDeque<HandlerCall>,
+    /// The owner of the handler that was called last.
+    running: Option<Uuid>,
 }
 
+///
+/// A handler registered to an event. Handlers run in descending `priority` and handlers of the same priority
+/// in the init order of their plugins. A plugin can register one handler per event.
+///
 #[derive(Clone)]
 pub struct StoredEventHandler {
-    handler: EventHandler,
+    handler: Handler,
+    /// Whether the handler declared itself long-running. It then runs on a thread of its own instead of the event pool.
+    long_running: bool,
     plugin_id: Uuid,
+    priority: i32,
+    /// The number of handlers registered before this one. It breaks the ties the priority and the owner leave.
+    sequence: u64,
 }
 
 impl Hash for StoredEventHandler {
@@ -68,57 +223,20 @@ impl PartialEq for StoredEventHandler {
 impl Eq for StoredEventHandler {}
 
 impl StoredEventHandler {
-    pub const fn new(handler: EventHandler, plugin_id: Uuid) -> Self {
-        Self { handler, plugin_id }
-    }
-}
-
-#[derive(PartialEq, Eq, Hash, Clone, Copy)]
-struct TopoNode(Uuid, Option<EventHandler>);
-
-impl TopoNode {
-    #[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
-    fn create_dependency_entry<'plugin, F, I>(
-        handler: &StoredEventHandler,
-        plugins: F,
-        stored_plugins: &HashMap<Uuid, Plugin>,
-    ) -> Option<(Self, HashSet<Self>)>
-    where
-        F: Fn() -> I,
-        I: Iterator<Item = &'plugin Plugin>,
-    {
-        let plugin = stored_plugins.get(&handler.plugin_id)?;
-        let node = Self(handler.plugin_id, Some(handler.handler));
-        let deps = plugin
-            .dependencies()
-            .iter()
-            .map(|dep| Self::find_plugin_by_name(plugins(), dep))
-            .map(|dep| Self::find_id_for_plugin(dep?, stored_plugins))
-            .collect::<Option<_>>()?;
-        Some((node, deps))
+    pub const fn handler(&self) -> &Handler {
+        &self.handler
     }
 
-    #[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
-    fn find_id_for_plugin(
-        plugin: &Plugin,
-        stored_plugins: &HashMap<Uuid, Plugin>,
-    ) -> Option<Self> {
-        stored_plugins.iter().find_map(|(key, value)| {
-            (value.name() == plugin.name()).then_some(Self(*key, None))
-        })
+    pub fn new(handler: Handler, plugin_id: Uuid, priority: i32) -> Self {
+        let sequence = REGISTRATIONS.fetch_add(1, Ordering::Relaxed);
+        Self { handler, long_running: false, plugin_id, priority, sequence }
     }
 
-    #[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
-    fn find_plugin_by_name<'plugin>(
-        mut plugins: impl Iterator<Item = &'plugin Plugin>,
-        name: &str,
-    ) -> Option<&'plugin Plugin> {
-        plugins.find(|plugin| plugin.name() == name)
+    pub const fn plugin_id(&self) -> Uuid {
+        self.plugin_id
     }
-
 }
 
-
 #[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
 pub fn register_core_events(events: &Events, core_id: Uuid) -> Result<(), RuntimeError> {
     let mut new_events = HashMap::new();
@@ -143,6 +261,34 @@ pub fn register_core_events(events: &Events, core_id: Uuid) -> Result<(), Runtim
             core_id,
         ),
     );
+    new_events.insert(
+        "core:handler_error".into(),
+        Event::new(
+            schema_from_file(include_str!("../../event/handler_error.json"))?,
+            core_id,
+        ),
+    );
+    new_events.insert(
+        "core:host_exited".into(),
+        Event::new(
+            schema_from_file(include_str!("../../event/host_exited.json"))?,
+            core_id,
+        ),
+    );
+    new_events.insert(
+        "core:load_report".into(),
+        Event::new(
+            schema_from_file(include_str!("../../event/load_report.json"))?,
+            core_id,
+        ),
+    );
+    new_events.insert(
+        "core:plugin_failed".into(),
+        Event::new(
+            schema_from_file(include_str!("../../event/plugin_failed.json"))?,
+            core_id,
+        ),
+    );
     new_events.insert(
         "core:power".into(),
         Event::new(
@@ -150,6 +296,13 @@ pub fn register_core_events(events: &Events, core_id: Uuid) -> Result<(), Runtim
             core_id,
         ),
     );
+    new_events.insert(
+        "core:unregister".into(),
+        Event::new(
+            schema_from_file(include_str!("../../event/unregister.json"))?,
+            core_id,
+        ),
+    );
     events.rcu(|map| HashMap::clone(map).union(new_events.clone()));
     Ok(())
 }
@@ -159,21 +312,114 @@ pub(super) fn register<T: AsRef<str>>(
     handler: EventHandlerFuncUnsafeFP,
     plugin_id: Uuid,
     event_name: T,
+    priority: i32,
 ) -> Result<EventHandler, ServiceError> {
     let event_handler = EventHandler::new_unsafe(handler, Uuid::new_v4());
-    let stored_handler = StoredEventHandler::new(event_handler, plugin_id);
+    insert_handler(Handler::Native(event_handler), plugin_id, event_name.as_ref(), priority)?;
+    Ok(event_handler)
+}
+
+///
+/// Adds the handler of the given plugin to the event.
+/// Handlers of plugins running in a process of their own are registered through this function as well.
+///
+pub fn insert_handler(handler: Handler, plugin_id: Uuid, event_name: &str, priority: i32) -> Result<(), ServiceError> {
+    let stored_handler = StoredEventHandler::new(handler, plugin_id, priority);
+
+    let gov = get_gov().error(CoreInternalError)?;
+    gov.loader()
+        .plugins()
+        .load()
+        .get(&plugin_id)
+        .filter(|plugin| !plugin.is_failed())
+        .error(ServiceError::NotFound)?;
+    gov.events()
+        .rcu_alter(event_name, |event| {
+            event
+                .handlers
+                .insert(stored_handler.clone())
+                .or_error(ServiceError::Duplicate)
+        })
+}
 
+///
+/// Adds a placeholder for the handler of a lazy plugin to the event.
+/// The event has to be registered already, so only events of the core and of plugins loaded earlier can be declared.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+pub fn insert_placeholder(plugin_name: &str, event_name: &str, placeholder_id: Uuid) -> Result<(), ServiceError> {
+    if event_name == "core:init" {
+        return Err(ServiceError::InvalidString);
+    }
+    let handler = Handler::Lazy { event_name: event_name.into(), placeholder_id, plugin_name: plugin_name.into() };
+    let stored_handler = StoredEventHandler::new(handler, placeholder_id, 0);
     get_gov()
         .error(CoreInternalError)?
         .events()
-        .rcu_alter(event_name.as_ref(), |event| {
+        .rcu_alter(event_name, |event| {
             event
                 .handlers
                 .insert(stored_handler.clone())
                 .or_error(ServiceError::Duplicate)
-        })?;
+        })
+}
 
-    Ok(event_handler)
+///
+/// Changes the priority of a handler registered by the requesting plugin.
+/// The handler is found by its id across all events.
+///
+#[trait_fn(RequestHandlerFunc for HandlerPriorityRequestHandler)]
+pub fn handle<'args, F: Fn() -> Result<ApplicationContext, ServiceError>, S: Into<Cow<'args, str>>, T: AsRef<str>>(
+    _: F,
+    plugin_name: T,
+    args: S,
+) -> Result<String, ServiceError> {
+    let priority_args = serde_json::from_str::<HandlerPriorityArgs>(&args.into()).error(ServiceError::InvalidJson)?;
+    let (event_name, stored_handler) = owned_handler(priority_args.handler_id, plugin_name.as_ref())?;
+    let updated = StoredEventHandler { priority: priority_args.priority, ..stored_handler };
+    get_gov().error(CoreInternalError)?.events().rcu_alter(event_name, |event| {
+        event.handlers.replace(updated.clone());
+        Ok(())
+    })?;
+    Ok(json!({}).to_string())
+}
+
+///
+/// Marks a handler of the given plugin as long-running, so that it runs on a thread of its own from its next call on.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+pub fn mark_long_running(handler_id: Uuid, plugin_name: &str) -> Result<(), ServiceError> {
+    let (event_name, stored_handler) = owned_handler(handler_id, plugin_name)?;
+    let updated = StoredEventHandler { long_running: true, ..stored_handler };
+    get_gov().error(CoreInternalError)?.events().rcu_alter(event_name, |event| {
+        event.handlers.replace(updated.clone());
+        Ok(())
+    })
+}
+
+///
+/// Finds a handler by its id across all events and checks that the plugin with the given name owns it.
+/// Returns the name of the event with the handler.
+///
+fn owned_handler(handler_id: Uuid, plugin_name: &str) -> Result<(Box<str>, StoredEventHandler), ServiceError> {
+    let gov = get_gov().error(CoreInternalError)?;
+    let (event_name, stored_handler) = gov
+        .events()
+        .load()
+        .iter()
+        .find_map(|(event_name, event)| {
+            event
+                .handlers
+                .iter()
+                .find(|stored_handler| stored_handler.handler.id() == handler_id)
+                .map(|stored_handler| (event_name.clone(), stored_handler.clone()))
+        })
+        .error(ServiceError::NotFound)?;
+    let owner = gov.loader().plugins().load().get(&stored_handler.plugin_id).map(|plugin| Box::<str>::from(plugin.name()));
+    if owner.as_deref() != Some(plugin_name) {
+        return Err(ServiceError::Unauthorized);
+    }
+    Ok((event_name, stored_handler))
 }
 
 #[trait_fn(EventHandlerUnregisterService for EventHandlerUnregister)]
@@ -206,23 +452,38 @@ pub(super) fn register<S: AsRef<str>, T: AsRef<str>>(
     event_schema: S,
     plugin_id: Uuid,
     event_name: T,
+    dispatch: EventDispatch,
+    max_concurrency: Option<NonZeroUsize>,
 ) -> Result<(), ServiceError> {
-    if event_name.as_ref().contains(':') {
+    insert(event_schema.as_ref(), plugin_id, event_name.as_ref(), Dispatch::new(dispatch.into(), max_concurrency))
+}
+
+///
+/// Adds the event of the given plugin, whose handlers are scheduled according to `dispatch`.
+/// Events of plugins running in a process of their own are registered through this function as well.
+///
+pub fn insert(event_schema: &str, plugin_id: Uuid, event_name: &str, dispatch: Dispatch) -> Result<(), ServiceError> {
+    if event_name.contains(':') {
         return Err(ServiceError::InvalidString);
     }
-    let argument_schema_json = serde_json::from_str(event_schema.as_ref()).error(ServiceError::InvalidJson)?;
+    let argument_schema_json = serde_json::from_str(event_schema).error(ServiceError::InvalidJson)?;
 
     let argument_validator =
         jsonschema::validator_for(&argument_schema_json).error(ServiceError::InvalidSchema)?;
-    let event = Event::new(argument_validator, plugin_id);
+    let mut event = Event::new(argument_validator, plugin_id);
+    event.set_dispatch(dispatch);
     let full_name = {
         let gov = get_gov().error(CoreInternalError)?;
         let plugins = gov.loader().plugins().load();
-        let plugin_name = plugins.get(&plugin_id).map(Plugin::name).error(ServiceError::NotFound)?;
-        if gov.events().load().contains_key(event_name.as_ref()) {
+        let plugin_name = plugins
+                .get(&plugin_id)
+                .filter(|plugin| !plugin.is_failed())
+                .map(Plugin::name)
+                .error(ServiceError::NotFound)?;
+        if gov.events().load().contains_key(event_name) {
             return Err(ServiceError::Duplicate);
         }
-        let full_name = format!("{}:{}", plugin_name, event_name.as_ref());
+        let full_name = format!("{plugin_name}:{event_name}");
         gov.events()
             .rcu(|map| map.update(full_name.clone().into(), event.clone()));
         full_name
@@ -246,7 +507,7 @@ pub(super) fn unregister<S: AsRef<str>>(
     plugin_id: Uuid,
     event_name: S,
 ) -> Result<(), ServiceError> {
-    {
+    let plugin_name = {
         let gov = get_gov().error(CoreInternalError)?;
         let events_guard = gov.events().load();
         let event = events_guard
@@ -258,9 +519,11 @@ pub(super) fn unregister<S: AsRef<str>>(
 
         gov.events()
             .rcu(|events| events.without(event_name.as_ref()));
-    }
+        let plugins = gov.loader().plugins().load();
+        plugins.get(&plugin_id).map(Plugin::name).error(ServiceError::NotFound)?.to_owned()
+    };
 
-    Ok(())
+    notify_unregister(&plugin_name, RemovalReason::Unregister, &[event_name.as_ref().into()], &[])
 }
 
 #[trait_fn(EventTriggerService for EventTrigger)]
@@ -273,60 +536,216 @@ pub(super) fn trigger<S: AsRef<str>, T: AsRef<str>>(
         PowerState::Shutdown | PowerState::Restart => return Err(ServiceError::ShutingDown),
         PowerState::Running | PowerState::Cancel => {}
     }
+    trigger_unchecked(plugin_id, event_name.as_ref(), args.as_ref().to_owned())
+}
 
-    let event_arguments_json = serde_json::from_str(args.as_ref()).error(ServiceError::InvalidJson)?;
-    let funcs = {
-        let gov = get_gov().error(CoreInternalError)?;
-        let events = gov.events().load();
-        let event = events
-            .get(event_name.as_ref())
-            .error(ServiceError::NotFound)?;
-        if event.plugin_id != plugin_id {
-            return Err(ServiceError::Unauthorized);
+///
+/// Triggers an event regardless of the power state.
+/// Used by the core to notify plugins while it is shutting down or restarting.
+///
+pub fn trigger_unchecked(plugin_id: Uuid, event_name: &str, args: String) -> Result<(), ServiceError> {
+    scheduling::schedule(event_name, checked_calls(plugin_id, event_name, &args)?, args, None)
+}
+
+///
+/// Checks that the plugin owns the event and the arguments are valid and collects the handlers of the event in the order they run.
+///
+pub fn checked_calls(plugin_id: Uuid, event_name: &str, args: &str) -> Result<HandlerCalls, ServiceError> {
+    let event_arguments_json = serde_json::from_str(args).error(ServiceError::InvalidJson)?;
+    let gov = get_gov().error(CoreInternalError)?;
+    let events = gov.events().load();
+    let event = events
+        .get(event_name)
+        .error(ServiceError::NotFound)?;
+    if event.plugin_id != plugin_id {
+        return Err(ServiceError::Unauthorized);
+    }
+
+    event
+        .argument_validator
+        .validate(&event_arguments_json)
+        .error(ServiceError::InvalidApi)?;
+    let plugins = gov.loader().plugins().load();
+    let handlers = sort_handlers(event.handlers.iter(), &plugins, &gov.loader().init_order().load());
+    Ok(with_plugins(handlers, &plugins))
+}
+
+///
+/// Triggers "core:init" only for the handler of the given plugin and returns the completion to wait for it with.
+/// Used for plugins that are loaded while the rest of the system is already running.
+///
+pub fn init_plugin(plugin_id: Uuid, args: String) -> Result<Arc<Completion>, ServiceError> {
+    let calls = owned_calls(plugin_id, "core:init")?;
+    let completion = Completion::new(calls.len());
+    run_on_pool("core:init", calls, args, Some(Arc::clone(&completion)))?;
+    Ok(completion)
+}
+
+///
+/// Collects the handlers of the given plugin for an event.
+///
+fn owned_calls(plugin_id: Uuid, event_name: &str) -> Result<HandlerCalls, ServiceError> {
+    let gov = get_gov().error(CoreInternalError)?;
+    let events = gov.events().load();
+    let handlers = events
+        .get(event_name)
+        .error(ServiceError::NotFound)?
+        .handlers
+        .iter()
+        .filter(|handler| handler.plugin_id == plugin_id)
+        .cloned()
+        .collect();
+    Ok(with_plugins(handlers, &gov.loader().plugins().load()))
+}
+
+///
+/// Calls the handlers one after another in a single job on the event pool regardless of the dispatch settings of the event.
+///
+fn run_on_pool(event_name: &str, calls: HandlerCalls, args: String, completion: Option<Arc<Completion>>) -> Result<(), ServiceError> {
+    let executor = get_gov().error(CoreInternalError)?.runtime().event_pool.clone();
+    let owned_event_name = Box::<str>::from(event_name);
+    executor.execute(move || run(&owned_event_name, calls, &args, completion));
+    Ok(())
+}
+
+///
+/// Calls a handler on the current thread and returns its result or `None` if it was skipped because its plugin failed.
+/// The library of the plugin stays loaded until the handler returned.
+/// Every error of the handler is reported once as a handler error. Errors that stand for a panic or crash of the handler
+/// are additionally counted as faults of its plugin, see `fault::is_crash`.
+/// Panics are only caught inside the plugin by the api built with its `std` feature; the core can't catch a panic unwinding
+/// out of a native handler.
+/// Placeholders of lazy plugins aren't reported themselves, because the handlers they forward to are.
+/// The call is tracked by the watchdog while it runs. The `pending` handlers of the same trigger are moved on if the handler is detached from the event pool.
+///
+pub fn call(
+    event_name: &str,
+    func: &Handler,
+    plugin: Option<&Plugin>,
+    args: &str,
+    pending: Option<PendingCalls>,
+) -> Option<Result<(), ServiceError>> {
+    if plugin.is_some_and(Plugin::is_failed) {
+        return None;
+    }
+    let owner = owner_name(func, plugin);
+    let invocation_id = watchdog::start(event_name, func.id(), &owner, pending);
+    let result = func.handle(args);
+    watchdog::finish(invocation_id);
+    if let Err(error) = result
+        && !matches!(func, Handler::Lazy { .. })
+    {
+        fault::report_handler_error(event_name, func.id(), &owner, plugin, error);
+        if let Some(faulted) = plugin
+            && fault::is_crash(error)
+        {
+            fault::record(faulted, error);
         }
+    }
+    Some(result)
+}
 
-        event
-            .argument_validator
-            .validate(&event_arguments_json)
-            .error(ServiceError::InvalidApi)?;
-        if event_name.as_ref() == "core:init" {
-            let Some(funcs) = sort_handlers(event.handlers.iter(), &gov.loader().plugins().load())
-            else {
-                return Err(ServiceError::CoreInternalError);
-            };
-            funcs
+///
+/// The name of the plugin owning a handler. Placeholders of lazy plugins are owned by their plugin and other handlers without a plugin by the core.
+///
+pub fn owner_name(func: &Handler, plugin: Option<&Plugin>) -> Box<str> {
+    match (plugin, func) {
+        (Some(owner), _) => owner.name().into(),
+        (None, Handler::Lazy { plugin_name, .. }) => plugin_name.clone(),
+        (None, Handler::Native(_) | Handler::Remote { .. }) => "core".into(),
+    }
+}
+
+///
+/// Calls the handlers one after another on the current thread. Long-running handlers are started on a thread of their own instead.
+/// Stops early if the remaining handlers were moved on, because the running handler was detached from the event pool.
+/// The outcome of every handler is recorded in the `completion` if one is given.
+///
+pub fn run(event_name: &str, calls: HandlerCalls, args: &str, completion: Option<Arc<Completion>>) {
+    let pending = PendingCalls {
+        args: args.into(),
+        completion,
+        event_name: event_name.into(),
+        queue: Arc::new(Mutex::new(PendingQueue { calls: calls.into(), running: None })),
+    };
+    while let Some((stored_handler, plugin)) = pending.next() {
+        if stored_handler.long_running {
+            spawn_dedicated(event_name, stored_handler, plugin, args, pending.completion.clone());
         } else {
-            event.handlers.iter().map(|stored_handler| stored_handler.handler).collect()
+            let outcome = call(event_name, &stored_handler.handler, plugin.as_ref(), args, Some(pending.clone()));
+            if let Some(finished) = &pending.completion {
+                finished.finish(stored_handler.plugin_id, outcome);
+            }
         }
-    };
-    let executor = get_gov().error(CoreInternalError)?.runtime().event_pool.clone();
-    let owned_args = args.as_ref().to_owned();
-    executor.execute(move || {
-        for func in funcs {
-            let _err = func.handle(ContextSupplierImpl, owned_args.clone()).error(ServiceError::PluginInternalError);
+    }
+}
+
+///
+/// Calls a long-running handler on a thread of its own, so that it doesn't occupy a thread of the event pool.
+///
+#[expect(clippy::print_stderr, reason = "handlers that can't be started are reported to the operator")]
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn spawn_dedicated(
+    event_name: &str,
+    stored_handler: StoredEventHandler,
+    plugin: Option<Plugin>,
+    args: &str,
+    completion: Option<Arc<Completion>>,
+) {
+    let handler_id = stored_handler.handler.id();
+    let owner_id = stored_handler.plugin_id;
+    let owned_event_name = Box::<str>::from(event_name);
+    let owned_args = Box::<str>::from(args);
+    if let Some(started) = &completion {
+        started.detach(owner_id);
+    }
+    let dedicated_completion = completion.clone();
+    let spawned = thread::Builder::new().name(LONG_RUNNING_THREAD_NAME.into()).spawn(move || {
+        let outcome = call(&owned_event_name, &stored_handler.handler, plugin.as_ref(), &owned_args, None);
+        if let Some(finished) = dedicated_completion {
+            finished.finish(owner_id, outcome);
         }
     });
-    Ok(())
+    if let Err(error) = spawned {
+        eprintln!("Handler {handler_id} could not be started on a thread of its own: {error}");
+        if let Some(skipped) = completion {
+            skipped.finish(owner_id, None);
+        }
+    }
 }
 
+///
+/// Orders the handlers by descending priority and handlers of the same priority by the init order of the plugins owning them.
+/// Handlers that don't belong to a plugin with a position in the init order, like those of the core or placeholders of lazy plugins, follow.
+/// The remaining ties are broken by the name of the owner and then by the order the handlers were registered in.
+///
 #[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
 fn sort_handlers<'handler>(
     handlers: impl Iterator<Item = &'handler StoredEventHandler>,
     stored_plugins: &HashMap<Uuid, Plugin>,
-) -> Option<Vec<EventHandler>> {
-    let nodes: Vec<_> = handlers
+    init_order: &HashMap<Box<str>, usize>,
+) -> Vec<StoredEventHandler> {
+    let mut ordered = handlers.cloned().collect::<Vec<_>>();
+    ordered.sort_by_cached_key(|handler| {
+        let owner = owner_name(&handler.handler, stored_plugins.get(&handler.plugin_id));
+        let position = stored_plugins.get(&handler.plugin_id).and_then(|plugin| init_order.get(plugin.name()).copied());
+        (Reverse(handler.priority), position.unwrap_or(usize::MAX), owner, handler.sequence)
+    });
+    ordered
+}
+
+///
+/// Pairs the handlers with the plugins owning them. Handlers of the core have no plugin.
+///
+fn with_plugins(
+    handlers: Vec<StoredEventHandler>,
+    stored_plugins: &HashMap<Uuid, Plugin>,
+) -> HandlerCalls {
+    handlers
+        .into_iter()
         .map(|handler| {
-            TopoNode::create_dependency_entry(handler, || stored_plugins.values(), stored_plugins)
+            let plugin = stored_plugins.get(&handler.plugin_id).cloned();
+            (handler, plugin)
         })
-        .collect::<Option<_>>()?;
-    let mut sorter = TopoSort::new();
-    for (node, deps) in nodes {
-        sorter.insert_from_set(node, deps);
-    }
-    
-        sorter
-            .iter()
-            .map(|node| node.ok()?.0.1)
-            .collect::<Option<_>>()
+        .collect()
 }
-
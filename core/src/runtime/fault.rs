@@ -0,0 +1,95 @@
+use plugin_loader_api::{ErrorMapper as _, ServiceError};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    governor::get_gov,
+    loader::Plugin,
+    runtime::{
+        cleanup::{RemovalReason, remove_registrations},
+        event::trigger_unchecked,
+    },
+};
+
+///
+/// Whether an error of a handler stands for a panic or crash rather than an ordinary error.
+/// Panics caught by the api of the plugin, traps of WASM modules and crashed host processes all surface as
+/// `ServiceError::PluginInternalError`. Calling a handler the plugin didn't provide fails with `ServiceError::NullFunctionPointer`.
+///
+pub const fn is_crash(error: ServiceError) -> bool {
+    matches!(error, ServiceError::PluginInternalError | ServiceError::NullFunctionPointer)
+}
+
+///
+/// Counts a fault of the given plugin. Only panics and crashes are faults (see `is_crash`), ordinary errors are
+/// counted as handler errors by `report_handler_error` alone.
+/// The plugin is quarantined once it reaches the `fault_limit` of the core settings.
+///
+#[expect(clippy::print_stderr, reason = "faults are reported to the operator")]
+pub fn record(plugin: &Plugin, error: ServiceError) {
+    let faults = plugin.add_fault();
+    eprintln!("Plugin \"{}\" faulted: {error}", plugin.name());
+    let Ok(gov) = get_gov() else {
+        return;
+    };
+    let fault_limit = gov.config().core_settings().unwrap_or_default().fault_limit();
+    if fault_limit == 0 || faults != fault_limit {
+        return;
+    }
+    if let Err(quarantine_error) = quarantine(plugin, faults) {
+        eprintln!("Plugin \"{}\" could not be quarantined: {quarantine_error}", plugin.name());
+    }
+}
+
+///
+/// Reports a handler that returned an error or panicked while handling an event.
+/// The error is counted for the plugin owning the handler, written to the log and announced with `core:handler_error`.
+/// Errors of the handlers of `core:handler_error` itself aren't announced again.
+///
+#[expect(clippy::print_stderr, reason = "handler errors are reported to the operator")]
+#[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+pub fn report_handler_error(event_name: &str, handler_id: Uuid, owner: &str, plugin: Option<&Plugin>, error: ServiceError) {
+    let errors = plugin.map(Plugin::add_handler_error);
+    eprintln!("Handler {handler_id} of \"{owner}\" failed on event \"{event_name}\": {error}");
+    if event_name == "core:handler_error" {
+        return;
+    }
+    let Ok(gov) = get_gov() else {
+        return;
+    };
+    let announced = trigger_unchecked(
+        gov.runtime().core_id(),
+        "core:handler_error",
+        json!({
+            "error": error.to_string(),
+            "errors": errors,
+            "event_name": event_name,
+            "handler_id": handler_id,
+            "plugin": owner
+        })
+        .to_string(),
+    );
+    if let Err(announce_error) = announced {
+        eprintln!("The error of handler {handler_id} could not be announced: {announce_error}");
+    }
+}
+
+///
+/// Marks the plugin as failed and removes its events, endpoints and handlers.
+/// The plugin stays loaded until it is reloaded or unloaded. The remaining plugins are informed with `core:plugin_failed`.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn quarantine(plugin: &Plugin, faults: usize) -> Result<(), ServiceError> {
+    plugin.mark_failed();
+    remove_registrations(plugin.id(), RemovalReason::Failure)?;
+    let core_id = get_gov().error(ServiceError::CoreInternalError)?.runtime().core_id();
+    trigger_unchecked(
+        core_id,
+        "core:plugin_failed",
+        json!({
+            "plugin": plugin.name(),
+            "faults": faults
+        })
+        .to_string(),
+    )
+}
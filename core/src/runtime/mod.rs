@@ -1,33 +1,49 @@
+pub mod blocking;
+pub mod cleanup;
 pub mod endpoint;
 pub mod event;
+pub mod fault;
+pub mod registry;
+pub mod remote;
+pub mod scheduling;
+pub mod watchdog;
 
-use core::{num::NonZero, sync::atomic::Ordering};
-use alloc::sync::Arc;
+use core::{
+    num::NonZero,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+use alloc::{collections::BTreeMap, sync::Arc};
 
 use std::{
     thread::{self, Thread},
+    time::Instant,
 };
 
 use crate::{
     config::{Config, ConfigError},
-    governor::{GOV, GovernorError, get_gov},
-    loader::{Loader, LoaderError},
+    governor::{GOV, Governor, GovernorError, get_gov},
+    loader::{Dependency, DependencyKind, Loader, LoaderError, Plugin, reload, report},
     runtime::{
+        blocking::EventTriggerBlocking,
+        cleanup::RemovalReason,
         endpoint::{EndpointRegister, EndpointRequest, EndpointUnregister},
         event::{
             EventHandlerRegister, EventHandlerUnregister, EventRegister, EventTrigger,
             EventUnregister,
         },
+        scheduling::Completion,
+        watchdog::{EVENT_POOL_THREAD_NAME, Watchdog},
     },
 };
 use atomic_enum::atomic_enum;
 use derive_more::Display;
 use plugin_loader_api::{
-    ApplicationContext, ServiceError, pointer_traits::{ContextSupplier, EventTriggerService as _, trait_fn}
+    ApplicationContext, OPTIONAL_FUNCTIONS, ServiceError, pointer_traits::{ContextSupplier, trait_fn}
 };
 use jsonschema::{ValidationError, Validator};
 use serde::{Deserialize, Serialize};
-use serde_json::json;
+use serde_json::{Value, json};
 use thiserror::Error;
 use threadpool::ThreadPool;
 use uuid::Uuid;
@@ -45,8 +61,11 @@ pub enum PowerState {
 pub struct Runtime {
     core_id: Uuid,
     event_pool: ThreadPool,
+    /// The number of threads of the `event_pool` without the threads added for handlers detached from it.
+    event_pool_size: AtomicUsize,
     main_handle: Thread,
     power_state: AtomicPowerState,
+    watchdog: Arc<Watchdog>,
 }
 
 #[derive(Debug, Display, Error)]
@@ -61,15 +80,14 @@ pub enum RuntimeError {
 
 impl Default for Runtime {
     fn default() -> Self {
+        let event_pool_size = default_event_pool_size();
         Self {
             core_id: Uuid::new_v4(),
             power_state: AtomicPowerState::new(PowerState::Running),
             main_handle: thread::current(),
-            event_pool: ThreadPool::new(
-                thread::available_parallelism()
-                .unwrap_or(NonZero::<usize>::MIN)
-                .into(),
-            ),
+            event_pool: ThreadPool::with_name(EVENT_POOL_THREAD_NAME.into(), event_pool_size),
+            event_pool_size: AtomicUsize::new(event_pool_size),
+            watchdog: Arc::default(),
         }
     }
 }
@@ -88,36 +106,77 @@ impl Runtime {
         self.core_id
     }
     
+    ///
+    /// Triggers "core:init" and waits until every init handler returned or declared itself long-running,
+    /// so that the handlers plugins register during their init are in place before the load report is published.
+    /// Stops waiting after the `init_timeout_ms` of the core settings and leaves the remaining handlers running.
+    ///
+    #[expect(clippy::print_stderr, reason = "init handlers that don't settle are reported to the operator")]
     #[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
     pub fn init() -> Result<(), RuntimeError> {
-        
         let core_id = get_gov()?.runtime().core_id();
-        let plugins = get_gov()?
-                .loader()
-                .plugins()
-                .load()
+        let args = Self::init_arguments()?;
+        let calls = event::checked_calls(core_id, "core:init", &args)?;
+        let completion = Completion::new(calls.len());
+        scheduling::schedule("core:init", calls, args, Some(&completion))?;
+        completion.wait_settled(Self::init_deadline()?)?;
+        if !completion.is_settled() {
+            eprintln!("Not every \"core:init\" handler returned within the init timeout, continuing the start without them");
+        }
+        Ok(())
+    }
+
+    ///
+    /// The arguments of the "core:init" event describing all currently loaded plugins.
+    ///
+    pub fn init_arguments() -> Result<String, RuntimeError> {
+        let loaded_plugins = get_gov()?.loader().plugins().load_full();
+        let loaded_versions = loaded_plugins.values().map(|plugin| (plugin.name(), plugin.version())).collect::<BTreeMap<_, _>>();
+        let plugins = loaded_plugins
                 .values()
-                .map(|plugin| json!({"name": *plugin.name(), "version": *plugin.version()}))
+                .map(|plugin| init_entry(plugin, &loaded_versions))
                 .collect::<Vec<_>>();
-        EventTrigger::trigger(
-            core_id,
-            "core:init",
-            json!({"core_version": env!("CARGO_PKG_VERSION"), "plugins": plugins}).to_string(),
-        )?;
-        Ok(())
+        Ok(json!({"core_version": env!("CARGO_PKG_VERSION"), "plugins": plugins}).to_string())
     }
     
+    ///
+    /// The end of the wait for "core:init" handlers given by the `init_timeout_ms` of the core settings.
+    ///
+    pub fn init_deadline() -> Result<Option<Instant>, RuntimeError> {
+        let timeout = get_gov()?.config().core_settings()?.init_timeout_ms();
+        Ok((timeout != 0).then(|| Instant::now() + Duration::from_millis(timeout)))
+    }
+
     #[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
     pub fn park() -> Result<PowerState, RuntimeError> {
         thread::park();
         Ok(get_gov()?.runtime().check_and_reset_power())
     }
     
+    ///
+    /// Waits for all running handlers and removes all plugins with "shutdown" as reason.
+    /// Waits again until the remaining plugins handled the "core:unregister" notifications.
+    ///
+    fn remove_plugins(gov: &Governor) -> Result<(), RuntimeError> {
+        gov.runtime().event_pool.join();
+        cleanup::remove_all_plugins(RemovalReason::Shutdown)?;
+        gov.runtime().event_pool.join();
+        Ok(())
+    }
+
+    ///
+    /// Gives the event pool an extra thread for every running handler detached from it and every thread of it waiting for other jobs.
+    ///
+    pub fn resize_event_pool(&self) {
+        let mut pool = self.event_pool.clone();
+        pool.set_num_threads(self.event_pool_size.load(Ordering::Relaxed).saturating_add(self.watchdog.extra_threads()));
+    }
+
     #[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
     pub fn restart() -> Result<(), RuntimeError> {
         let mut old_config_dir = None;
         if let Some(gov) = &*GOV.load() {
-            gov.runtime().event_pool.join();
+            Self::remove_plugins(gov)?;
             old_config_dir.clone_from(&Some(Box::from(gov.config().config_dir()?)));
         }
         
@@ -128,6 +187,15 @@ impl Runtime {
         Self::start()
     }
 
+    ///
+    /// Sets the number of threads of the event pool. 0 uses one thread per available CPU.
+    ///
+    pub fn set_event_pool_size(&self, size: usize) {
+        let event_pool_size = if size == 0 { default_event_pool_size() } else { size };
+        self.event_pool_size.store(event_pool_size, Ordering::Relaxed);
+        self.resize_event_pool();
+    }
+
     pub fn set_power(&self, power_state: PowerState) {
         self.power_state.store(power_state, Ordering::Relaxed);
         if power_state != PowerState::Cancel {
@@ -136,18 +204,86 @@ impl Runtime {
     }
 
     #[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+    #[expect(clippy::print_stderr, reason = "failed cleanups are reported to the operator")]
     pub fn shutdown() {
-        if let Some(gov) = &*GOV.load() {
-            gov.runtime().event_pool.join();
+        if let Some(gov) = &*GOV.load()
+            && let Err(error) = Self::remove_plugins(gov)
+        {
+            eprintln!("Plugins could not be removed on shutdown: {error}");
         }
         GOV.rcu(|_| None);
     }
     
     pub fn start() -> Result<(), RuntimeError> {
         Config::init()?;
+        let gov = get_gov()?;
+        gov.runtime().set_event_pool_size(gov.config().core_settings()?.event_pool_size());
         Loader::load_libraries()?;
-        Self::init()
+        Loader::validate_dependencies()?;
+        watchdog::watch()?;
+        Self::init()?;
+        report::publish()?;
+        if get_gov()?.config().core_settings()?.watch_plugins() {
+            reload::watch_plugins()?;
+        }
+        Ok(())
     }
+
+    pub const fn watchdog(&self) -> &Arc<Watchdog> {
+        &self.watchdog
+    }
+}
+
+///
+/// One thread of the event pool per available CPU.
+///
+fn default_event_pool_size() -> usize {
+    thread::available_parallelism()
+        .unwrap_or(NonZero::<usize>::MIN)
+        .into()
+}
+
+///
+/// Describes a loaded plugin for the "core:init" event and the "core:plugins" endpoint including which of its optional dependencies are active.
+/// An optional dependency is active if it is loaded in a version satisfying its requirement.
+///
+fn init_entry(plugin: &Plugin, loaded_versions: &BTreeMap<&str, &str>) -> Value {
+    let names_of = |kind| {
+        plugin.dependencies()
+            .iter()
+            .filter(|dependency| dependency.kind() == kind)
+            .map(Dependency::name)
+            .collect::<Vec<_>>()
+    };
+    let optional_dependencies = plugin.dependencies()
+        .iter()
+        .filter(|dependency| dependency.kind() == DependencyKind::Optional)
+        .map(|dependency| {
+            let active = loaded_versions.get(dependency.name()).is_some_and(|version| dependency.matches(version));
+            json!({"name": dependency.name(), "active": active})
+        })
+        .collect::<Vec<_>>();
+    let unavailable_functions = OPTIONAL_FUNCTIONS
+        .iter()
+        .map(|(function, _)| *function)
+        .filter(|function| !plugin.api_version().offers(function))
+        .collect::<Vec<_>>();
+    json!({
+        "id": plugin.stable_id(),
+        "name": plugin.name(),
+        "version": plugin.version(),
+        "api_version": plugin.api_version().to_string(),
+        "unavailable_functions": unavailable_functions,
+        "dependencies": names_of(DependencyKind::Required),
+        "optional_dependencies": optional_dependencies,
+        "load_after": names_of(DependencyKind::After),
+        "load_before": names_of(DependencyKind::Before),
+        "description": plugin.details().description,
+        "authors": plugin.details().authors,
+        "license": plugin.details().license,
+        "homepage": plugin.details().homepage,
+        "metadata": plugin.details().metadata,
+    })
 }
 
 fn schema_from_file(file: &str) -> Result<Validator, RuntimeError> {
@@ -165,5 +301,6 @@ fn supply() -> ApplicationContext {
         EndpointRegister,
         EndpointUnregister,
         EndpointRequest,
+        EventTriggerBlocking,
     >()
 }
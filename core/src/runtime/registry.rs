@@ -0,0 +1,70 @@
+use alloc::{borrow::Cow, collections::BTreeMap};
+
+use plugin_loader_api::{
+    ApplicationContext, ErrorMapper as _, ServiceError,
+    pointer_traits::{RequestHandlerFunc, trait_fn},
+};
+use serde::Deserialize;
+use serde_json::{Value, json};
+
+use crate::{
+    governor::get_gov,
+    runtime::{cleanup::owned_names, endpoint::Endpoint, event::Event, init_entry},
+};
+
+#[derive(Deserialize)]
+struct PluginsArgs {
+    name: Option<Box<str>>,
+}
+
+///
+/// Lists the loaded plugins with everything they currently own in the registry of the `Governor`.
+///
+#[trait_fn(RequestHandlerFunc for PluginsRequestHandler)]
+pub fn handle<'args, F: Fn() -> Result<ApplicationContext, ServiceError>, S: Into<Cow<'args, str>>, T: AsRef<str>>(
+    _: F,
+    _: T,
+    args: S,
+) -> Result<String, ServiceError> {
+    let plugins_args = serde_json::from_str::<PluginsArgs>(&args.into()).error(ServiceError::InvalidJson)?;
+    let gov = get_gov().error(ServiceError::CoreInternalError)?;
+    let plugins = gov.loader().plugins().load_full();
+    let events = gov.events().load_full();
+    let endpoints = gov.endpoints().load_full();
+    let loaded_versions = plugins.values().map(|plugin| (plugin.name(), plugin.version())).collect::<BTreeMap<_, _>>();
+    let mut selected = plugins
+        .values()
+        .filter(|plugin| plugins_args.name.as_deref().is_none_or(|name| plugin.name() == name))
+        .collect::<Vec<_>>();
+    selected.sort_by_key(|plugin| plugin.name());
+    let entries = selected
+        .into_iter()
+        .map(|plugin| {
+            let plugin_id = plugin.id();
+            let mut entry = init_entry(plugin, &loaded_versions);
+            if let Some(fields) = entry.as_object_mut() {
+                fields.insert("status".into(), json!(if plugin.is_failed() { "failed" } else { "loaded" }));
+                fields.insert("handler_errors".into(), json!(plugin.handler_errors()));
+                fields.insert("events".into(), json!(sorted(owned_names(&events, |event: &Event| event.plugin_id() == plugin_id))));
+                fields.insert(
+                    "endpoints".into(),
+                    json!(sorted(owned_names(&endpoints, |endpoint: &Endpoint| endpoint.plugin_id() == plugin_id))),
+                );
+                fields.insert(
+                    "handlers".into(),
+                    json!(sorted(owned_names(&events, |event: &Event| {
+                        event.handlers().iter().any(|handler| handler.plugin_id() == plugin_id)
+                    }))),
+                );
+            }
+            entry
+        })
+        .collect::<Vec<Value>>();
+    Ok(json!({"plugins": entries}).to_string())
+}
+
+fn sorted(names: Box<[Box<str>]>) -> Box<[Box<str>]> {
+    let mut sorted_names = names;
+    sorted_names.sort();
+    sorted_names
+}
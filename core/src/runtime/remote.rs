@@ -0,0 +1,113 @@
+use alloc::sync::Arc;
+use core::time::Duration;
+
+use plugin_loader_api::{
+    ErrorMapper as _, ServiceError,
+    pointer_traits::{
+        EndpointRequestService as _, EndpointUnregisterService as _, EventHandlerUnregisterService as _,
+        EventTriggerBlockingService as _, EventTriggerService as _, EventUnregisterService as _,
+    },
+};
+use serde_json::Value;
+use uuid::Uuid;
+
+use crate::{
+    rpc::{
+        self, Peer, RegisterEndpointParams, RegisterEventParams, RegisterHandlerParams,
+        RequestEndpointParams, TriggerEventBlockingParams, TriggerEventParams, UnregisterEndpointParams, UnregisterEventParams,
+        UnregisterHandlerParams, method,
+    },
+    runtime::{
+        blocking::EventTriggerBlocking,
+        endpoint::{self, EndpointRequest, EndpointUnregister, RequestHandler},
+        event::{
+            self, EventHandlerUnregister, EventTrigger, EventUnregister, Handler,
+            insert_handler,
+        },
+        scheduling::Dispatch,
+    },
+};
+
+///
+/// Serves a call of a plugin that isn't a native library in the loader process with the services of the core.
+/// Calls are only accepted for the plugin the peer belongs to.
+///
+#[expect(clippy::cognitive_complexity, reason = "one arm per method of the protocol")]
+pub fn serve(
+    peer: &Arc<dyn Peer>,
+    plugin_id: Uuid,
+    method_name: &str,
+    params: Value,
+) -> Result<Value, ServiceError> {
+    match method_name {
+        method::REGISTER_ENDPOINT => {
+            let call = rpc::decode::<RegisterEndpointParams>(params)?;
+            authorize(plugin_id, call.plugin_id)?;
+            let handler = RequestHandler::Remote { handler_id: call.handler_id, peer: Arc::clone(peer) };
+            endpoint::insert(
+                &call.argument_schema.to_string(),
+                &call.response_schema.to_string(),
+                plugin_id,
+                &call.endpoint_name,
+                handler,
+            )?;
+        }
+        method::REGISTER_EVENT => {
+            let call = rpc::decode::<RegisterEventParams>(params)?;
+            authorize(plugin_id, call.plugin_id)?;
+            let dispatch = Dispatch::new(call.dispatch, call.max_concurrency);
+            event::insert(&call.argument_schema.to_string(), plugin_id, &call.event_name, dispatch)?;
+        }
+        method::REGISTER_HANDLER => {
+            let call = rpc::decode::<RegisterHandlerParams>(params)?;
+            authorize(plugin_id, call.plugin_id)?;
+            let handler = Handler::Remote { handler_id: call.handler_id, peer: Arc::clone(peer) };
+            insert_handler(handler, plugin_id, &call.event_name, call.priority)?;
+        }
+        method::REQUEST_ENDPOINT => {
+            let call = rpc::decode::<RequestEndpointParams>(params)?;
+            authorize(plugin_id, call.plugin_id)?;
+            let response = EndpointRequest::request(call.endpoint_name, plugin_id, call.args.to_string())?;
+            return serde_json::from_str(&response).error(ServiceError::InvalidJson);
+        }
+        method::TRIGGER_EVENT => {
+            let call = rpc::decode::<TriggerEventParams>(params)?;
+            authorize(plugin_id, call.plugin_id)?;
+            EventTrigger::trigger(plugin_id, call.event_name, call.args.to_string())?;
+        }
+        method::TRIGGER_EVENT_BLOCKING => {
+            let call = rpc::decode::<TriggerEventBlockingParams>(params)?;
+            authorize(plugin_id, call.plugin_id)?;
+            let timeout = call.timeout_ms.map(Duration::from_millis);
+            let results = EventTriggerBlocking::trigger_blocking(plugin_id, call.event_name, call.args.to_string(), timeout)?;
+            return serde_json::from_str(&results).error(ServiceError::InvalidJson);
+        }
+        method::UNREGISTER_ENDPOINT => {
+            let call = rpc::decode::<UnregisterEndpointParams>(params)?;
+            authorize(plugin_id, call.plugin_id)?;
+            EndpointUnregister::unregister(plugin_id, call.endpoint_name)?;
+        }
+        method::UNREGISTER_EVENT => {
+            let call = rpc::decode::<UnregisterEventParams>(params)?;
+            authorize(plugin_id, call.plugin_id)?;
+            EventUnregister::unregister(plugin_id, call.event_name)?;
+        }
+        method::UNREGISTER_HANDLER => {
+            let call = rpc::decode::<UnregisterHandlerParams>(params)?;
+            authorize(plugin_id, call.plugin_id)?;
+            EventHandlerUnregister::unregister(call.handler_id, plugin_id, call.event_name)?;
+        }
+        _ => return Err(ServiceError::NotFound),
+    }
+    Ok(Value::Null)
+}
+
+///
+/// A plugin outside of the loader process may only act as itself.
+///
+fn authorize(plugin_id: Uuid, claimed_id: Uuid) -> Result<(), ServiceError> {
+    if plugin_id != claimed_id {
+        return Err(ServiceError::Unauthorized);
+    }
+    Ok(())
+}
@@ -0,0 +1,387 @@
+use alloc::{
+    borrow::Cow,
+    collections::{BTreeMap, BTreeSet, VecDeque},
+    sync::Arc,
+};
+use core::num::NonZeroUsize;
+use std::{
+    sync::{Condvar, Mutex, MutexGuard},
+    time::Instant,
+};
+
+use plugin_loader_api::{
+    ApplicationContext, ErrorMapper as _, EventDispatch, ServiceError,
+    pointer_traits::{RequestHandlerFunc, trait_fn},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use threadpool::ThreadPool;
+use uuid::Uuid;
+
+use crate::{
+    governor::get_gov,
+    loader::Plugin,
+    runtime::{event::{self, HandlerCalls}, watchdog},
+    util::ArcMapExt as _,
+};
+
+use ServiceError::CoreInternalError;
+
+type Job = Box<dyn FnOnce() + Send>;
+///
+/// The outcomes of the handlers of a trigger by the id of the plugin owning them, which is unique among the handlers of an event.
+/// A handler that was skipped has the outcome `None`.
+///
+pub type Outcomes = BTreeMap<Uuid, Option<Result<(), ServiceError>>>;
+
+///
+/// Tracks the handlers of a trigger until they returned, so that the trigger can be waited for.
+/// Handlers leaving the event pool to run long-running are settled without having returned.
+///
+pub struct Completion {
+    changed: Condvar,
+    /// The number of handlers of the trigger.
+    handlers: usize,
+    state: Mutex<CompletionState>,
+}
+
+impl Completion {
+    ///
+    /// Marks the handler of the given owner as running long-running. Waiting for it to be settled doesn't wait for it to return.
+    ///
+    pub fn detach(&self, owner_id: Uuid) {
+        if let Ok(mut state) = self.state.lock()
+            && !state.outcomes.contains_key(&owner_id)
+        {
+            state.detached.insert(owner_id);
+            self.changed.notify_all();
+        }
+    }
+
+    ///
+    /// Records the result of the handler of the given owner or `None` if it was skipped.
+    ///
+    pub fn finish(&self, owner_id: Uuid, outcome: Option<Result<(), ServiceError>>) {
+        if let Ok(mut state) = self.state.lock() {
+            state.detached.remove(&owner_id);
+            state.outcomes.insert(owner_id, outcome);
+            self.changed.notify_all();
+        }
+    }
+
+    ///
+    /// Whether every handler returned or left the event pool to run long-running.
+    ///
+    pub fn is_settled(&self) -> bool {
+        self.state.lock().is_ok_and(|state| state.is_settled(self.handlers))
+    }
+
+    pub fn new(handlers: usize) -> Arc<Self> {
+        Arc::new(Self { changed: Condvar::new(), handlers, state: Mutex::default() })
+    }
+
+    ///
+    /// Waits until every handler returned or the deadline passed and returns the outcomes of the handlers that returned.
+    /// Unlike `wait_settled` this waits for long-running handlers as well.
+    ///
+    pub fn wait(&self, deadline: Option<Instant>) -> Result<Outcomes, ServiceError> {
+        self.wait_until(deadline, |state| state.outcomes.len() >= self.handlers).map(|state| state.outcomes.clone())
+    }
+
+    ///
+    /// Waits until every handler returned or left the event pool to run long-running, or the deadline passed,
+    /// and returns the outcomes of the returned handlers. Whether the deadline passed first tells `is_settled`.
+    ///
+    pub fn wait_settled(&self, deadline: Option<Instant>) -> Result<Outcomes, ServiceError> {
+        self.wait_until(deadline, |state| state.is_settled(self.handlers)).map(|state| state.outcomes.clone())
+    }
+
+    ///
+    /// Waits until `done` holds or the deadline passed. The event pool has an extra thread meanwhile.
+    ///
+    fn wait_until(
+        &self,
+        deadline: Option<Instant>,
+        done: impl Fn(&CompletionState) -> bool,
+    ) -> Result<MutexGuard<'_, CompletionState>, ServiceError> {
+        watchdog::while_blocked(|| {
+            let mut state = self.state.lock().error(CoreInternalError)?;
+            while !done(&state) {
+                state = match deadline {
+                    Some(end) => {
+                        let remaining = end.saturating_duration_since(Instant::now());
+                        if remaining.is_zero() {
+                            break;
+                        }
+                        self.changed.wait_timeout(state, remaining).error(CoreInternalError)?.0
+                    }
+                    None => self.changed.wait(state).error(CoreInternalError)?,
+                };
+            }
+            Ok(state)
+        })
+    }
+}
+
+#[derive(Default)]
+struct CompletionState {
+    /// The handlers that run long-running and didn't return yet.
+    detached: BTreeSet<Uuid>,
+    outcomes: Outcomes,
+}
+
+impl CompletionState {
+    fn is_settled(&self, handlers: usize) -> bool {
+        self.outcomes.len().saturating_add(self.detached.len()) >= handlers
+    }
+}
+
+#[derive(Deserialize)]
+struct EventDispatchArgs {
+    event_name: Box<str>,
+    max_concurrency: Option<NonZeroUsize>,
+    mode: DispatchMode,
+}
+
+///
+/// How the handlers of an event are scheduled on the event pool.
+///
+#[derive(Clone, Default)]
+pub struct Dispatch {
+    /// Limits the jobs of the event running at the same time. `None` if the event has no limit.
+    limiter: Option<Arc<Limiter>>,
+    mode: DispatchMode,
+}
+
+impl Dispatch {
+    pub fn new(mode: DispatchMode, max_concurrency: Option<NonZeroUsize>) -> Self {
+        Self {
+            limiter: max_concurrency.map(|max| Arc::new(Limiter { max_concurrency: max, state: Mutex::default() })),
+            mode,
+        }
+    }
+}
+
+///
+/// Decides whether the handlers of one trigger run in a single job of the event pool or in a job each.
+///
+#[derive(Clone, Copy, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DispatchMode {
+    /// Every handler runs in a job of its own, so that slow handlers don't delay the others.
+    Parallel,
+    /// The handlers run one after another in a single job in the order of their priority.
+    #[default]
+    Sequential,
+}
+
+impl From<EventDispatch> for DispatchMode {
+    fn from(dispatch: EventDispatch) -> Self {
+        match dispatch {
+            EventDispatch::Parallel => Self::Parallel,
+            EventDispatch::Sequential | _ => Self::Sequential,
+        }
+    }
+}
+
+///
+/// Queues the jobs of an event once `max_concurrency` of them are running.
+/// A job is a single handler in the parallel mode and all handlers of one trigger in the sequential mode.
+///
+pub struct Limiter {
+    max_concurrency: NonZeroUsize,
+    state: Mutex<LimiterState>,
+}
+
+impl Limiter {
+    ///
+    /// Runs the queued jobs on the current thread of the event pool until the queue is empty.
+    ///
+    fn drain(&self) {
+        loop {
+            let next = {
+                let Ok(mut state) = self.state.lock() else {
+                    return;
+                };
+                let Some(job) = state.queued.pop_front() else {
+                    state.running = state.running.saturating_sub(1);
+                    return;
+                };
+                job
+            };
+            next();
+        }
+    }
+
+    ///
+    /// Executes the job on the event pool or queues it if `max_concurrency` jobs are already running.
+    ///
+    fn submit(self: &Arc<Self>, pool: &ThreadPool, job: Job) -> Result<(), ServiceError> {
+        let mut state = self.state.lock().error(CoreInternalError)?;
+        if state.running >= self.max_concurrency.get() {
+            state.queued.push_back(job);
+            return Ok(());
+        }
+        state.running = state.running.saturating_add(1);
+        drop(state);
+        let limiter = Arc::clone(self);
+        pool.execute(move || {
+            job();
+            limiter.drain();
+        });
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct LimiterState {
+    queued: VecDeque<Job>,
+    running: usize,
+}
+
+///
+/// Changes how the handlers of an event of the requesting plugin are scheduled.
+/// Names without a plugin prefix refer to the events of the requesting plugin.
+///
+#[trait_fn(RequestHandlerFunc for EventDispatchRequestHandler)]
+pub fn handle<'args, F: Fn() -> Result<ApplicationContext, ServiceError>, S: Into<Cow<'args, str>>, T: AsRef<str>>(
+    _: F,
+    plugin_name: T,
+    args: S,
+) -> Result<String, ServiceError> {
+    let dispatch_args = serde_json::from_str::<EventDispatchArgs>(&args.into()).error(ServiceError::InvalidJson)?;
+    let plugin_id = get_gov()
+        .error(CoreInternalError)?
+        .loader()
+        .plugins()
+        .load()
+        .values()
+        .find(|plugin| plugin.name() == plugin_name.as_ref())
+        .map(Plugin::id)
+        .error(ServiceError::NotFound)?;
+    let event_name = if dispatch_args.event_name.contains(':') {
+        dispatch_args.event_name
+    } else {
+        format!("{}:{}", plugin_name.as_ref(), dispatch_args.event_name).into()
+    };
+    configure(&event_name, plugin_id, dispatch_args.mode, dispatch_args.max_concurrency)?;
+    Ok(json!({}).to_string())
+}
+
+///
+/// Sets how the handlers of an event owned by the given plugin are scheduled.
+/// Triggers that are already scheduled keep the previous settings.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn configure(
+    event_name: &str,
+    plugin_id: Uuid,
+    mode: DispatchMode,
+    max_concurrency: Option<NonZeroUsize>,
+) -> Result<(), ServiceError> {
+    get_gov().error(CoreInternalError)?.events().rcu_alter(event_name, |event| {
+        if event.plugin_id() != plugin_id {
+            return Err(ServiceError::Unauthorized);
+        }
+        event.set_dispatch(Dispatch::new(mode, max_concurrency));
+        Ok(())
+    })
+}
+
+///
+/// Schedules the handlers of a trigger on the event pool according to the dispatch settings of the event.
+/// The outcome of every handler is recorded in the `completion` if one is given.
+///
+pub fn schedule(
+    event_name: &str,
+    calls: HandlerCalls,
+    args: String,
+    completion: Option<&Arc<Completion>>,
+) -> Result<(), ServiceError> {
+    let gov = get_gov().error(CoreInternalError)?;
+    let dispatch = gov.events().load().get(event_name).map(|event| event.dispatch().clone()).unwrap_or_default();
+    let jobs = match dispatch.mode {
+        DispatchMode::Parallel => calls.into_iter().map(|call| vec![call]).collect(),
+        DispatchMode::Sequential => vec![calls],
+    };
+    let shared_event_name = Arc::<str>::from(event_name);
+    let shared_args = Arc::<str>::from(args);
+    let pool = &gov.runtime().event_pool;
+    for job_calls in jobs {
+        let job_event_name = Arc::clone(&shared_event_name);
+        let job_args = Arc::clone(&shared_args);
+        let job_completion = completion.cloned();
+        let job: Job = Box::new(move || event::run(&job_event_name, job_calls, &job_args, job_completion));
+        match &dispatch.limiter {
+            Some(limiter) => limiter.submit(pool, job)?,
+            None => pool.execute(job),
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use alloc::sync::Arc;
+    use core::{
+        num::NonZeroUsize,
+        sync::atomic::{AtomicUsize, Ordering},
+        time::Duration,
+    };
+    use std::{sync::Mutex, thread};
+
+    use threadpool::ThreadPool;
+
+    use super::{Job, Limiter};
+
+    fn limiter(max_concurrency: usize) -> Option<Arc<Limiter>> {
+        Some(Arc::new(Limiter { max_concurrency: NonZeroUsize::new(max_concurrency)?, state: Mutex::default() }))
+    }
+
+    #[test]
+    fn limiter_caps_running_jobs() {
+        let pool = ThreadPool::new(4);
+        let running = Arc::new(AtomicUsize::new(0));
+        let peak = Arc::new(AtomicUsize::new(0));
+        let mut accepted = 0usize;
+        if let Some(limited) = limiter(2) {
+            for _ in 0usize..8usize {
+                let job_running = Arc::clone(&running);
+                let job_peak = Arc::clone(&peak);
+                let job: Job = Box::new(move || {
+                    let now = job_running.fetch_add(1, Ordering::SeqCst).saturating_add(1);
+                    job_peak.fetch_max(now, Ordering::SeqCst);
+                    thread::sleep(Duration::from_millis(10));
+                    job_running.fetch_sub(1, Ordering::SeqCst);
+                });
+                accepted = accepted.saturating_add(usize::from(limited.submit(&pool, job).is_ok()));
+            }
+        }
+        pool.join();
+        assert_eq!(accepted, 8, "every job is accepted even if it has to be queued");
+        assert_eq!(peak.load(Ordering::SeqCst), 2, "no more than max_concurrency jobs run at the same time");
+    }
+
+    #[test]
+    fn limiter_runs_queued_jobs_in_order() {
+        let pool = ThreadPool::new(2);
+        let finished = Arc::new(Mutex::new(Vec::new()));
+        if let Some(limited) = limiter(1) {
+            for index in 0i32..5i32 {
+                let job_finished = Arc::clone(&finished);
+                let job: Job = Box::new(move || {
+                    if let Ok(mut order) = job_finished.lock() {
+                        order.push(index);
+                    }
+                });
+                assert!(limited.submit(&pool, job).is_ok(), "the job is accepted");
+            }
+        }
+        pool.join();
+        assert_eq!(
+            finished.lock().ok().map(|order| order.clone()),
+            Some(vec![0i32, 1i32, 2i32, 3i32, 4i32]),
+            "queued jobs run in the order they were submitted"
+        );
+    }
+}
@@ -0,0 +1,295 @@
+use alloc::{borrow::Cow, sync::Arc};
+use core::{
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::Duration,
+};
+use std::{
+    thread::{self, ThreadId},
+    time::Instant,
+};
+
+use plugin_loader_api::{
+    ApplicationContext, ErrorMapper as _, ServiceError,
+    pointer_traits::{RequestHandlerFunc, trait_fn},
+};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uuid::Uuid;
+
+use crate::{
+    governor::get_gov,
+    runtime::event::{self, PendingCalls},
+    util::LockedMap,
+};
+
+use ServiceError::CoreInternalError;
+
+pub const EVENT_POOL_THREAD_NAME: &str = "event-pool";
+pub const LONG_RUNNING_THREAD_NAME: &str = "long-running-handler";
+const MAX_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+
+#[derive(Deserialize)]
+struct LongRunningArgs {
+    handler_id: Option<Uuid>,
+}
+
+///
+/// A running call of an event handler.
+///
+#[derive(Clone)]
+pub struct Invocation {
+    /// Whether the call was declared long-running while it occupied a thread of the event pool. The pool got an extra thread in turn.
+    detached: Arc<AtomicBool>,
+    event_name: Box<str>,
+    handler_id: Uuid,
+    /// The handlers of the same trigger that weren't called yet. `None` if the call doesn't run on the event pool.
+    pending: Option<PendingCalls>,
+    placement: Placement,
+    plugin: Box<str>,
+    started: Instant,
+    thread: ThreadId,
+    warned: Arc<AtomicBool>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Placement {
+    /// The handler runs on the thread that triggered or requested it, like the thread of a blocking trigger.
+    Caller,
+    /// The handler declared itself long-running and runs on a thread of its own.
+    Dedicated,
+    /// The handler runs on a thread of the event pool.
+    Pool,
+}
+
+///
+/// Tracks the running calls of event handlers with the time they started.
+///
+#[derive(Default)]
+pub struct Watchdog {
+    /// The number of threads waiting for jobs of the event pool.
+    blocked: AtomicUsize,
+    /// The number of running calls detached from the event pool.
+    detached: AtomicUsize,
+    running: LockedMap<Uuid, Invocation>,
+}
+
+impl Watchdog {
+    ///
+    /// The number of threads the event pool has more than its configured size, one for every detached call and thread waiting for it.
+    ///
+    pub fn extra_threads(&self) -> usize {
+        self.detached.load(Ordering::Relaxed).saturating_add(self.blocked.load(Ordering::Relaxed))
+    }
+}
+
+///
+/// Declares a handler of the requesting plugin long-running. It runs on a thread of its own from its next call on.
+/// Without a `handler_id` the handler of the requesting plugin running on the calling thread is meant.
+/// Calls of the handler that currently occupy a thread of the event pool are detached: the pool gets an extra thread
+/// and the handlers triggered after them are moved on.
+///
+#[trait_fn(RequestHandlerFunc for LongRunningRequestHandler)]
+pub fn handle<'args, F: Fn() -> Result<ApplicationContext, ServiceError>, S: Into<Cow<'args, str>>, T: AsRef<str>>(
+    _: F,
+    plugin_name: T,
+    args: S,
+) -> Result<String, ServiceError> {
+    let long_running_args = serde_json::from_str::<LongRunningArgs>(&args.into()).error(ServiceError::InvalidJson)?;
+    let gov = get_gov().error(CoreInternalError)?;
+    let watchdog = gov.runtime().watchdog();
+    let running = watchdog.running.load();
+    let current = thread::current().id();
+    let handler_id = match long_running_args.handler_id {
+        Some(handler_id) => handler_id,
+        None => running
+            .values()
+            .find(|invocation| invocation.thread == current && &*invocation.plugin == plugin_name.as_ref())
+            .map(|invocation| invocation.handler_id)
+            .error(ServiceError::NotFound)?,
+    };
+    event::mark_long_running(handler_id, plugin_name.as_ref())?;
+    for invocation in running.values().filter(|invocation| invocation.handler_id == handler_id && invocation.placement == Placement::Pool) {
+        if invocation.detached.swap(true, Ordering::Relaxed) {
+            continue;
+        }
+        watchdog.detached.fetch_add(1, Ordering::Relaxed);
+        gov.runtime().resize_event_pool();
+        if let Some(pending) = &invocation.pending {
+            pending.resume_elsewhere()?;
+        }
+    }
+    Ok(json!({}).to_string())
+}
+
+///
+/// Whether the current thread runs a long-running handler, either on a thread of its own or detached from the event pool.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+pub fn is_long_running() -> bool {
+    let Ok(gov) = get_gov() else {
+        return false;
+    };
+    let current = thread::current().id();
+    gov.runtime().watchdog().running.load().values().any(|invocation| {
+        invocation.thread == current
+            && (invocation.placement == Placement::Dedicated || invocation.detached.load(Ordering::Relaxed))
+    })
+}
+
+///
+/// Whether the current thread runs a handler of the given plugin for the event.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+pub fn is_handling(event_name: &str, plugin: &str) -> bool {
+    let Ok(gov) = get_gov() else {
+        return false;
+    };
+    let current = thread::current().id();
+    gov.runtime()
+        .watchdog()
+        .running
+        .load()
+        .values()
+        .any(|invocation| invocation.thread == current && &*invocation.event_name == event_name && &*invocation.plugin == plugin)
+}
+
+///
+/// Lists the running calls of event handlers, the longest running first.
+///
+#[trait_fn(RequestHandlerFunc for RunningHandlersRequestHandler)]
+pub fn handle<'args, F: Fn() -> Result<ApplicationContext, ServiceError>, S: Into<Cow<'args, str>>, T: AsRef<str>>(
+    _: F,
+    _: T,
+    _: S,
+) -> Result<String, ServiceError> {
+    let gov = get_gov().error(CoreInternalError)?;
+    let running = gov.runtime().watchdog().running.load();
+    let mut invocations = running.values().collect::<Vec<_>>();
+    invocations.sort_by_key(|invocation| invocation.started);
+    let handlers = invocations
+        .into_iter()
+        .map(|invocation| {
+            json!({
+                "event_name": invocation.event_name,
+                "handler_id": invocation.handler_id,
+                "long_running": invocation.placement == Placement::Dedicated || invocation.detached.load(Ordering::Relaxed),
+                "placement": invocation.placement,
+                "plugin": invocation.plugin,
+                "running_ms": u64::try_from(invocation.started.elapsed().as_millis()).unwrap_or(u64::MAX)
+            })
+        })
+        .collect::<Vec<_>>();
+    Ok(json!({"handlers": handlers}).to_string())
+}
+
+///
+/// Starts tracking a call of a handler on the current thread and returns the id of the call.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+pub fn start(event_name: &str, handler_id: Uuid, plugin: &str, pending: Option<PendingCalls>) -> Uuid {
+    let invocation_id = Uuid::new_v4();
+    let Ok(gov) = get_gov() else {
+        return invocation_id;
+    };
+    let current = thread::current();
+    let placement = match current.name() {
+        Some(EVENT_POOL_THREAD_NAME) => Placement::Pool,
+        Some(LONG_RUNNING_THREAD_NAME) => Placement::Dedicated,
+        Some(_) | None => Placement::Caller,
+    };
+    let invocation = Invocation {
+        detached: Arc::default(),
+        event_name: event_name.into(),
+        handler_id,
+        pending: pending.filter(|_| placement == Placement::Pool),
+        placement,
+        plugin: plugin.into(),
+        started: Instant::now(),
+        thread: current.id(),
+        warned: Arc::default(),
+    };
+    gov.runtime().watchdog().running.rcu(|running| running.update(invocation_id, invocation.clone()));
+    invocation_id
+}
+
+///
+/// Stops tracking a call. The event pool shrinks back if the call was detached from it.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+pub fn finish(invocation_id: Uuid) {
+    let Ok(gov) = get_gov() else {
+        return;
+    };
+    let watchdog = gov.runtime().watchdog();
+    let finished = watchdog.running.load().get(&invocation_id).cloned();
+    watchdog.running.rcu(|running| running.without(&invocation_id));
+    if finished.is_some_and(|invocation| invocation.detached.load(Ordering::Relaxed)) {
+        watchdog.detached.fetch_sub(1, Ordering::Relaxed);
+        gov.runtime().resize_event_pool();
+    }
+}
+
+///
+/// Runs `wait` with an extra thread in the event pool, so that the jobs it waits for find a free thread even if
+/// the pool is occupied by handlers that themselves wait for the caller, for example through a request of a plugin process.
+///
+pub fn while_blocked<T, W: FnOnce() -> T>(wait: W) -> T {
+    let gov = get_gov().ok().map(|gov| Arc::clone(&gov));
+    if let Some(blocked) = &gov {
+        blocked.runtime().watchdog().blocked.fetch_add(1, Ordering::Relaxed);
+        blocked.runtime().resize_event_pool();
+    }
+    let result = wait();
+    if let Some(blocked) = &gov {
+        blocked.runtime().watchdog().blocked.fetch_sub(1, Ordering::Relaxed);
+        blocked.runtime().resize_event_pool();
+    }
+    result
+}
+
+///
+/// Starts the thread that warns about calls of handlers running longer than the `slow_handler_ms` of the core settings.
+/// Long-running handlers aren't warned about. The thread stops with the runtime it was started for.
+///
+#[expect(clippy::single_call_fn, reason = "function extracted to locate to better module")]
+pub fn watch() -> Result<(), ServiceError> {
+    let gov = get_gov().error(CoreInternalError)?;
+    let threshold = Duration::from_millis(gov.config().core_settings().error(CoreInternalError)?.slow_handler_ms());
+    if threshold.is_zero() {
+        return Ok(());
+    }
+    let watchdog = Arc::downgrade(gov.runtime().watchdog());
+    thread::Builder::new()
+        .name("watchdog".into())
+        .spawn(move || {
+            while let Some(alive) = watchdog.upgrade() {
+                warn_slow(&alive, threshold);
+                drop(alive);
+                thread::sleep(threshold.min(MAX_CHECK_INTERVAL));
+            }
+        })
+        .error(CoreInternalError)?;
+    Ok(())
+}
+
+///
+/// Warns once about every call that runs longer than the threshold and wasn't declared long-running.
+///
+#[expect(clippy::print_stderr, reason = "slow handlers are reported to the operator")]
+#[expect(clippy::single_call_fn, reason = "function extracted for visibility")]
+fn warn_slow(watchdog: &Watchdog, threshold: Duration) {
+    for invocation in watchdog.running.load().values() {
+        let long_running = invocation.placement == Placement::Dedicated || invocation.detached.load(Ordering::Relaxed);
+        if long_running || invocation.started.elapsed() < threshold || invocation.warned.swap(true, Ordering::Relaxed) {
+            continue;
+        }
+        eprintln!(
+            "Handler {} of \"{}\" has been running on event \"{}\" for more than {}ms",
+            invocation.handler_id,
+            invocation.plugin,
+            invocation.event_name,
+            threshold.as_millis()
+        );
+    }
+}
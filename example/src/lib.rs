@@ -16,8 +16,8 @@ use std::{
 use arc_swap::ArcSwapOption;
 use atomic_enum::atomic_enum;
 use plugin_loader_api::{
-    API_VERSION, ApplicationContext, ErrorMapper as _, PluginInfo, ServiceError,
-    pointer_traits::{EventHandlerFunc, plugin_main, trait_fn},
+    API_VERSION, ApplicationContext, ErrorMapper as _, PluginInfo, PluginMetadata, ServiceError,
+    pointer_traits::{EventHandlerFunc, plugin_main, plugin_metadata, trait_fn},
 };
 use serde::Deserialize;
 use serde_json::json;
@@ -52,6 +52,18 @@ fn main(uuid: Uuid) -> PluginInfo {
     PluginInfo::new::<InitTest, _, _, _>("ExamplePlugin", "0.0.2", [], API_VERSION)
 }
 
+#[expect(clippy::single_call_fn, reason = "metadata function")]
+#[plugin_metadata]
+fn metadata() -> PluginMetadata {
+    PluginMetadata::new(
+        "An example plugin testing the services of the loader.",
+        ["RedIODev".into()],
+        env!("CARGO_PKG_LICENSE"),
+        "",
+        json!({"category": "example"}).to_string(),
+    )
+}
+
 #[trait_fn(EventHandlerFunc for PowerListener)]
 fn handle<'args, F: Fn() -> Result<ApplicationContext, ServiceError>, S: Into<Cow<'args, str>>>(
     context: F,
@@ -72,7 +84,8 @@ fn handle<'args, F: Fn() -> Result<ApplicationContext, ServiceError>, S: Into<Co
     
         println!("Plugin: Init: Test from plugin! Args:{}", args.into());
         let uuid = **UUID.load().as_ref().error(ServiceError::PluginInternalError)?;
-        context()?.register_event_handler::<PowerListener,_>(uuid, "core:power")?;
+        context()?.register_event_handler_with_priority::<PowerListener>(uuid, "core:power", 10)?;
+        context()?.declare_long_running(uuid, None)?;
         println!("before while loop with {:?}", POWER.load(Ordering::Relaxed));
         while POWER.load(Ordering::Relaxed) < MyPow::Shutdown {
             let mut input = String::new();
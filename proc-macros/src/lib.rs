@@ -1,13 +1,15 @@
 //!
-//! Proc macros for the plugin-loader
+//! Proc macros for the plugin-loader.
 //! 
 
+#![allow(clippy::missing_inline_in_public_items, reason = "proc macros are only called by the compiler")]
+
 use core::{fmt::Display, mem::MaybeUninit, array, fmt};
 
 use proc_macro::{Span, TokenStream};
 use quote::quote;
 use syn::{
-    Attribute, BareFnArg, BareVariadic, FnArg, GenericParam, Generics, Ident, ItemFn, ItemImpl, ItemTrait, ItemType, PatType, Signature, Token, TraitItem, TraitItemFn, TypeBareFn, Variadic, Visibility, parse::Parse, parse_macro_input, parse_quote, punctuated::Punctuated, token::Comma
+    Attribute, BareFnArg, BareVariadic, FnArg, GenericParam, Generics, Ident, ItemFn, ItemImpl, ItemTrait, ItemType, LitStr, PatType, Path, Signature, Token, TraitItem, TraitItemFn, TypeBareFn, Variadic, Visibility, parse::Parse, parse_macro_input, parse_quote, punctuated::Punctuated, token::Comma
 };
 
 struct AttrArgs {
@@ -27,6 +29,30 @@ impl Parse for AttrArgs {
     }
 }
 
+///
+/// The arguments of the `static_plugin` macro: the name of the plugin and optionally its metadata function.
+///
+struct StaticPluginArgs {
+    metadata_fn: Option<Path>,
+    name: LitStr,
+}
+
+impl Parse for StaticPluginArgs {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let name = input.parse()?;
+        let metadata_fn = if input.is_empty() {
+            None
+        } else {
+            let _: Token![,] = input.parse()?;
+            Some(input.parse()?)
+        };
+        Ok(Self {
+            metadata_fn,
+            name,
+        })
+    }
+}
+
 trait ArrayIter: Iterator {
     fn collect_array<const N: usize>(self) -> Result<[Self::Item;N], ArrayBoundsError> where Self: Sized{
         let collector: ArrayCollector<_,_> = self.collect();
@@ -101,6 +127,65 @@ pub fn plugin_main(_attr: TokenStream, item: TokenStream) -> TokenStream {
     }.into()
 }
 
+///
+/// A simple macro that generates the optional metadata entrypoint for a plugin from a function returning a `PluginMetadata`.
+/// 
+#[proc_macro_attribute]
+pub fn plugin_metadata(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    let item_fn  = parse_macro_input!(item as ItemFn);
+    let item_name = item_fn.sig.ident.clone();
+    quote! {
+        #[unsafe(no_mangle)]
+        pub unsafe extern "C" fn plugin_metadata() -> plugin_loader_api::CPluginMetadata {
+            #item_name().into()
+        }
+
+        #item_fn
+    }.into()
+}
+
+///
+/// A macro that links the annotated main function of a plugin into the binary instead of exporting it as `plugin_main`.
+/// 
+/// The loader finds all such plugins through `STATIC_PLUGINS` and treats them like plugins loaded from a library.
+/// The name of the plugin has to be passed as ```#[static_plugin("<name>")]``` and has to be the name the main function returns.
+/// The loader refers to the plugin by it before calling the main function, for example to decide whether it is enabled.
+/// The name of a function returning the `PluginMetadata` of the plugin can follow as ```#[static_plugin("<name>", <metadata_fn>)]```.
+/// 
+#[proc_macro_attribute]
+pub fn static_plugin(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let StaticPluginArgs { metadata_fn, name } = parse_macro_input!(attr as StaticPluginArgs);
+    let item_fn  = parse_macro_input!(item as ItemFn);
+    let item_name = item_fn.sig.ident.clone();
+    let (metadata_entry, metadata) = metadata_fn.map_or_else(
+        || (quote! {}, quote! { None }),
+        |path| (
+            quote! {
+                unsafe extern "C" fn static_plugin_metadata() -> plugin_loader_api::CPluginMetadata {
+                    #path().into()
+                }
+            },
+            quote! { Some(static_plugin_metadata) },
+        ),
+    );
+    quote! {
+        const _: () = {
+            unsafe extern "C" fn static_plugin_main(uuid: plugin_loader_api::CUuid) -> plugin_loader_api::CPluginInfo {
+                #item_name(uuid.into()).into()
+            }
+
+            #metadata_entry
+
+            #[plugin_loader_api::linkme::distributed_slice(plugin_loader_api::STATIC_PLUGINS)]
+            #[linkme(crate = plugin_loader_api::linkme)]
+            static STATIC_PLUGIN: plugin_loader_api::StaticPlugin =
+                plugin_loader_api::StaticPlugin::new(static_plugin_main, #metadata, #name);
+        };
+
+        #item_fn
+    }.into()
+}
+
 ///
 /// A free function can be annotated with this attribute and a small parameter expression.
 /// 